@@ -0,0 +1,102 @@
+//! Compares the slice search functions against the old `SliceExt::find`, which was a naive
+//! `windows(..).position(..)` search.
+//!
+//! Run with `cargo bench --bench find`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use wutil::{
+    prelude::*,
+    slice::{Finder, memchr},
+};
+
+/// The implementation of `SliceExt::find` before it used Two-Way.
+fn old_find<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Runs `f` repeatedly for about half a second and prints the average time per run.
+fn bench<R>(name: &str, mut f: impl FnMut() -> R) {
+    let mut runs = 0u32;
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_millis(500) {
+        black_box(f());
+        runs += 1;
+    }
+
+    println!("{name:<40} {:>12.2?}", start.elapsed() / runs);
+}
+
+fn compare(case: &str, haystack: &[u8], needle: &[u8]) {
+    println!("{case}:");
+
+    let finder = Finder::new(needle);
+    assert_eq!(finder.find(haystack), old_find(haystack, needle));
+
+    bench("  old SliceExt::find", || {
+        old_find(black_box(haystack), black_box(needle))
+    });
+    bench("  new SliceExt::find", || {
+        black_box(haystack).find(black_box(needle))
+    });
+    bench("  Finder::find", || {
+        black_box(&finder).find(black_box(haystack))
+    });
+    bench("  Finder::new + Finder::find", || {
+        Finder::new(black_box(needle)).find(black_box(haystack))
+    });
+}
+
+fn main() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(20_000);
+    let text = text.as_bytes();
+
+    println!("single byte:");
+    bench("  iter().position()", || {
+        black_box(text).iter().position(|&b| b == b'!')
+    });
+    bench("  memchr", || memchr(b'!', black_box(text)));
+    bench("  old SliceExt::find", || old_find(black_box(text), b"!"));
+    bench("  new SliceExt::find", || black_box(text).find(b"!"));
+    bench("  Finder::new_bytes + Finder::find", || {
+        Finder::new_bytes(black_box(b"!")).find(black_box(text))
+    });
+
+    compare("english text, absent needle", text, b"lazy cat");
+    compare(
+        "english text, needle at end",
+        &[text, b"lazy cat"].concat(),
+        b"lazy cat",
+    );
+
+    let repetitive = vec![b'a'; 1 << 18];
+    compare(
+        "repetitive, a^64 b",
+        &repetitive,
+        &[&[b'a'; 64][..], b"b"].concat(),
+    );
+    compare(
+        "repetitive, b a^64",
+        &repetitive,
+        &[&b"b"[..], &[b'a'; 64]].concat(),
+    );
+
+    // Floats are only `PartialEq`, so they can't use `Finder`
+    let floats: Vec<f64> = repetitive.iter().map(|&b| f64::from(b)).collect();
+    let needle: Vec<f64> = [&[b'a'; 64][..], b"b"]
+        .concat()
+        .into_iter()
+        .map(f64::from)
+        .collect();
+    println!("floats, a^64 b:");
+    bench("  old SliceExt::find", || {
+        old_find(black_box(&floats), black_box(&needle))
+    });
+    bench("  new SliceExt::find", || {
+        black_box(&floats[..]).find(black_box(&needle))
+    });
+}
@@ -0,0 +1,290 @@
+#!/usr/bin/env python3
+"""Generates `src/str/unicode/tables.rs` from the Unicode Character Database.
+
+Every file is pinned to `UNICODE_VERSION`. Missing files are downloaded from
+https://www.unicode.org/Public/ into the given directory (`target/ucd` by default):
+
+    python3 scripts/unicode.py [path/to/ucd]
+    cargo fmt
+
+The files used are:
+
+- `auxiliary/GraphemeBreakProperty.txt` for the `Grapheme_Cluster_Break` property
+- `emoji/emoji-data.txt` for `Extended_Pictographic`, `Emoji_Presentation` and `Emoji_Modifier`
+- `DerivedCoreProperties.txt` for `Indic_Conjunct_Break` and the zero width properties
+- `extracted/DerivedEastAsianWidth.txt` for wide characters
+- `HangulSyllableType.txt` and `PropList.txt` for the remaining zero width characters
+
+The test data in `tests/data` (`GraphemeBreakTest.txt` and `emoji-test.txt`) must be copied
+unmodified from the same version.
+
+The Unicode data is licensed under the Unicode License v3, which is in `LICENSE-UNICODE`.
+"""
+
+import re
+import sys
+import urllib.request
+from pathlib import Path
+
+UNICODE_VERSION = (17, 0, 0)
+
+ROOT = Path(__file__).resolve().parent.parent
+OUTPUT = ROOT / "src" / "str" / "unicode" / "tables.rs"
+
+NUM_CODEPOINTS = 0x110000
+
+# Names of `GraphemeCategory` variants by their `Grapheme_Cluster_Break` value
+CATEGORIES = {
+    "CR": "Cr",
+    "LF": "Lf",
+    "Control": "Control",
+    "Extend": "Extend",
+    "ZWJ": "Zwj",
+    "Regional_Indicator": "RegionalIndicator",
+    "Prepend": "Prepend",
+    "SpacingMark": "SpacingMark",
+    "L": "L",
+    "V": "V",
+    "T": "T",
+    "LV": "Lv",
+    "LVT": "Lvt",
+}
+
+
+def version_str():
+    return ".".join(map(str, UNICODE_VERSION))
+
+
+def fetch(ucd, path):
+    """Gets the text of a UCD file, downloading it first if it isn't in `ucd`."""
+    local = ucd / Path(path).name
+
+    if not local.exists():
+        url = f"https://www.unicode.org/Public/{version_str()}/ucd/{path}"
+        ucd.mkdir(parents=True, exist_ok=True)
+        urllib.request.urlretrieve(url, local)
+
+    text = local.read_text(encoding="utf-8")
+    header = "\n".join(text.splitlines()[:10])
+
+    # UCD files name their version in the first line, emoji files in a comment below it
+    emoji_version = re.search(r"Version:? (\d+\.\d+)", header)
+    major_minor = ".".join(map(str, UNICODE_VERSION[:2]))
+    if f"-{version_str()}.txt" not in header and (
+        emoji_version is None or emoji_version.group(1) != major_minor
+    ):
+        sys.exit(f"{local} is not from Unicode {version_str()}")
+
+    return text
+
+
+def properties(text):
+    """Yields the code point range and value of every entry in a UCD property file. Values with
+    several fields, like `InCB; Linker`, are joined with `=`."""
+    for line in text.splitlines():
+        line = line.split("#", 1)[0].strip()
+        if not line:
+            continue
+
+        code_points, *value = [field.strip() for field in line.split(";")]
+        lo, _, hi = code_points.partition("..")
+        yield int(lo, 16), int(hi or lo, 16), "=".join(value)
+
+
+def missing(text):
+    """Yields the code point range and value of every `@missing` line in a UCD property file, which
+    give the default values of code points that aren't listed."""
+    for lo, hi, value in re.findall(r"^# @missing: (\w+)\.\.(\w+); (\w+)$", text, re.M):
+        yield int(lo, 16), int(hi, 16), value
+
+
+def code_points(text, value):
+    """Gets every code point that has `value` in a UCD property file."""
+    return [
+        cp for lo, hi, v in properties(text) if v == value for cp in range(lo, hi + 1)
+    ]
+
+
+def to_ranges(values):
+    """Merges a list of values indexed by code point into `(lo, hi, value)` ranges, leaving out
+    code points whose value is `None`. Ranges are merged across the surrogates."""
+    ranges = []
+
+    for cp, value in enumerate(values):
+        if value is None or 0xD800 <= cp < 0xE000:
+            continue
+
+        adjacent = ranges and (
+            ranges[-1][1] == cp - 1 or ranges[-1][1] == 0xD7FF and cp == 0xE000
+        )
+        if adjacent and ranges[-1][2] == value:
+            ranges[-1][1] = cp
+        else:
+            ranges.append([cp, cp, value])
+
+    return ranges
+
+
+def grapheme_tables(ucd):
+    break_property = fetch(ucd, "auxiliary/GraphemeBreakProperty.txt")
+    emoji_data = fetch(ucd, "emoji/emoji-data.txt")
+    core_properties = fetch(ucd, "DerivedCoreProperties.txt")
+
+    categories = [None] * NUM_CODEPOINTS
+
+    def assign(cp, category):
+        if categories[cp] is not None:
+            sys.exit(f"U+{cp:04X} is both {categories[cp]} and {category}")
+        categories[cp] = category
+
+    for lo, hi, value in properties(break_property):
+        for cp in range(lo, hi + 1):
+            assign(cp, CATEGORIES[value])
+
+    # No character with these properties has a `Grapheme_Cluster_Break` other than `Other`
+    for cp in code_points(emoji_data, "Extended_Pictographic"):
+        assign(cp, "ExtendedPictographic")
+    for cp in code_points(core_properties, "InCB=Consonant"):
+        assign(cp, "InCbConsonant")
+
+    incb_extend = [None] * NUM_CODEPOINTS
+    for cp in code_points(core_properties, "InCB=Extend"):
+        incb_extend[cp] = True
+
+    incb_linkers = code_points(core_properties, "InCB=Linker")
+
+    return (
+        to_ranges(categories),
+        [(lo, hi) for lo, hi, _ in to_ranges(incb_extend)],
+        incb_linkers,
+    )
+
+
+def width_table(ucd):
+    """Computes the width of every character on its own, following the rules of the
+    `unicode-width` crate for text that isn't East Asian. Only widths other than 1 are kept."""
+    east_asian_width = fetch(ucd, "extracted/DerivedEastAsianWidth.txt")
+    core_properties = fetch(ucd, "DerivedCoreProperties.txt")
+    syllable_types = fetch(ucd, "HangulSyllableType.txt")
+    prop_list = fetch(ucd, "PropList.txt")
+    break_property = fetch(ucd, "auxiliary/GraphemeBreakProperty.txt")
+    emoji_data = fetch(ucd, "emoji/emoji-data.txt")
+
+    # Code points that aren't listed are neutral, so they have a width of 1, except for unassigned
+    # code points in the CJK blocks
+    widths = [1] * NUM_CODEPOINTS
+    for lo, hi, value in missing(east_asian_width):
+        if value == "Wide":
+            widths[lo : hi + 1] = [2] * (hi + 1 - lo)
+
+    for lo, hi, value in properties(east_asian_width):
+        if value in ("W", "F"):
+            widths[lo : hi + 1] = [2] * (hi + 1 - lo)
+
+    zero_width = set()
+    zero_width.update(code_points(core_properties, "Default_Ignorable_Code_Point"))
+    zero_width.update(code_points(core_properties, "Grapheme_Extend"))
+    # Vowel and trailing jamo combine with a leading jamo into one wide syllable
+    zero_width.update(code_points(syllable_types, "V"))
+    zero_width.update(code_points(syllable_types, "T"))
+    # Prepended concatenation marks are visible, except for these
+    zero_width.update([0x070F, 0x0605, 0x0890, 0x0891, 0x08E2])
+    zero_width.update(
+        set(code_points(break_property, "Prepend"))
+        - set(code_points(prop_list, "Prepended_Concatenation_Mark"))
+    )
+    # DEVANAGARI CARET
+    zero_width.add(0xA8FA)
+    # HANGUL CHOSEONG FILLER starts a wide syllable, TIFINAGH CONSONANT JOINER is only invisible
+    # between two consonants
+    zero_width -= {0x115F, 0x2D7F}
+
+    for cp in zero_width:
+        widths[cp] = 0
+
+    for cp in code_points(emoji_data, "Emoji_Presentation"):
+        widths[cp] = 2
+    for cp in code_points(emoji_data, "Emoji_Modifier"):
+        widths[cp] = 2
+
+    # Characters that `unicode-width` gives a width depending on the characters around them
+    for cp in [0x1A10, 0x2D6F, 0xA4FC, 0xA4FD, 0x10C03, 0x16D67, 0x16D68]:
+        widths[cp] = 1
+    for cp in range(0x2D31, 0x2D66):
+        widths[cp] = 1
+    for cp in code_points(prop_list, "Regional_Indicator"):
+        widths[cp] = 1
+    # KHMER INDEPENDENT VOWEL QAAU and KHMER SIGN BEYYAL
+    widths[0x17A4] = 2
+    widths[0x17D8] = 3
+
+    # Control characters have no width
+    for cp in [*range(0x20), *range(0x7F, 0xA0)]:
+        widths[cp] = 0
+
+    return to_ranges([None if w == 1 else w for w in widths])
+
+
+def char(cp):
+    return f"'\\u{{{cp:x}}}'"
+
+
+def write_table(out, name, ty, entries):
+    out.append(f"pub(super) const {name}: &[{ty}] = &[")
+    line = "   "
+    for entry in entries:
+        item = f" {entry},"
+        if len(line) + len(item) > 100:
+            out.append(line)
+            line = "   "
+        line += item
+    out.append(line)
+    out.append("];")
+    out.append("")
+
+
+def main():
+    ucd = Path(sys.argv[1]) if len(sys.argv) > 1 else ROOT / "target" / "ucd" / version_str()
+
+    categories, incb_extend, incb_linkers = grapheme_tables(ucd)
+    widths = width_table(ucd)
+
+    out = [
+        "// This file was generated by `scripts/unicode.py` from the Unicode Character Database,",
+        f"// version {version_str()}. Do not edit it directly.",
+        "//",
+        "// The Unicode data is © Unicode, Inc. and licensed under the Unicode License v3, which is in",
+        "// `LICENSE-UNICODE`.",
+        "",
+        "use super::GraphemeCategory::{self, *};",
+        "",
+        f"pub const UNICODE_VERSION: (u8, u8, u8) = {UNICODE_VERSION};",
+        "",
+    ]
+
+    write_table(
+        out,
+        "GRAPHEME_CATEGORIES",
+        "(char, char, GraphemeCategory)",
+        [f"({char(lo)}, {char(hi)}, {cat})" for lo, hi, cat in categories],
+    )
+    write_table(
+        out,
+        "INCB_EXTEND",
+        "(char, char)",
+        [f"({char(lo)}, {char(hi)})" for lo, hi in incb_extend],
+    )
+    write_table(out, "INCB_LINKERS", "char", [char(c) for c in incb_linkers])
+    write_table(
+        out,
+        "WIDTHS",
+        "(char, char, u8)",
+        [f"({char(lo)}, {char(hi)}, {w})" for lo, hi, w in widths],
+    )
+
+    OUTPUT.parent.mkdir(parents=True, exist_ok=True)
+    OUTPUT.write_text("\n".join(out).rstrip() + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,407 @@
+//! Contains [`ArrayString`], a string with a fixed capacity that doesn't need an allocator.
+
+use core::{
+    borrow::{Borrow, BorrowMut},
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    ops::{Deref, DerefMut, RangeBounds},
+};
+
+use crate::array_vec::{self, ArrayVec, CapacityError, resolve_range};
+
+/// A string that stores up to `N` bytes inline.
+/// # Example
+/// ```rust
+/// # use wutil::ArrayString;
+/// use core::fmt::Write;
+///
+/// let mut message = ArrayString::<16>::new();
+/// write!(message, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+///
+/// assert_eq!(message, "1 + 2 = 3");
+/// assert!(message.try_push_str("and some more").is_err());
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArrayString<const N: usize> {
+    /// UTF-8 encoded bytes
+    bytes: ArrayVec<u8, N>,
+}
+
+impl<const N: usize> ArrayString<N> {
+    /// Creates an empty string.
+    pub const fn new() -> Self {
+        Self {
+            bytes: ArrayVec::new(),
+        }
+    }
+
+    /// Gets the length of the string in bytes.
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the string has no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Gets the maximum length of the string in bytes, which is always `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Gets the number of bytes that can still be added to the string.
+    pub const fn remaining_capacity(&self) -> usize {
+        self.bytes.remaining_capacity()
+    }
+
+    /// Gets the contents of the string as a `str`.
+    pub const fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    /// Gets the contents of the string as a mutable `str`.
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        unsafe { core::str::from_utf8_unchecked_mut(self.bytes.as_mut_slice()) }
+    }
+
+    /// Gets the UTF-8 bytes of the string.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Adds a `char` to the end of the string.
+    /// # Panics
+    /// Panics if there isn't enough room.
+    pub fn push(&mut self, c: char) {
+        assert!(self.try_push(c).is_ok(), "ArrayString is full");
+    }
+
+    /// Adds a `char` to the end of the string. Returns it back if there isn't enough room.
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        self.bytes
+            .try_extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())
+            .map_err(|_| CapacityError::new(c))
+    }
+
+    /// Appends `s` to the end of the string.
+    /// # Panics
+    /// Panics if there isn't enough room.
+    pub fn push_str(&mut self, s: &str) {
+        assert!(self.try_push_str(s).is_ok(), "ArrayString is full");
+    }
+
+    /// Appends `s` to the end of the string. Returns `s` back without adding any of it if there
+    /// isn't enough room.
+    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        self.bytes
+            .try_extend_from_slice(s.as_bytes())
+            .map_err(|_| CapacityError::new(s))
+    }
+
+    /// Removes the last `char` of the string.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.chars().next_back()?;
+        self.bytes.truncate(self.len() - c.len_utf8());
+
+        Some(c)
+    }
+
+    /// Inserts a `char` at the byte index `index`.
+    /// # Panics
+    /// Panics if `index` isn't a char boundary or if there isn't enough room.
+    pub fn insert(&mut self, index: usize, c: char) {
+        assert!(self.try_insert(index, c).is_ok(), "ArrayString is full");
+    }
+
+    /// Inserts a `char` at the byte index `index`. Returns it back if there isn't enough room.
+    /// # Panics
+    /// Panics if `index` isn't a char boundary.
+    pub fn try_insert(&mut self, index: usize, c: char) -> Result<(), CapacityError<char>> {
+        assert!(self.is_char_boundary(index), "index is not a char boundary");
+
+        self.try_push(c)?;
+        self.bytes[index..].rotate_right(c.len_utf8());
+
+        Ok(())
+    }
+
+    /// Removes the `char` at the byte index `index`.
+    /// # Panics
+    /// Panics if `index` isn't a char boundary or is the end of the string.
+    pub fn remove(&mut self, index: usize) -> char {
+        let c = self[index..]
+            .chars()
+            .next()
+            .expect("cannot remove a char from the end of a string");
+
+        self.bytes.drain(index..index + c.len_utf8());
+        c
+    }
+
+    /// Shortens the string to `len` bytes. Does nothing if the string is already shorter.
+    /// # Panics
+    /// Panics if `len` isn't a char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            assert!(
+                self.is_char_boundary(len),
+                "new length is not a char boundary"
+            );
+            self.bytes.truncate(len);
+        }
+    }
+
+    /// Removes the contents of the string.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Removes the byte range `range` and returns an iterator over its `char`s.
+    /// # Panics
+    /// Panics if `range` is out of bounds or its bounds aren't char boundaries.
+    /// # Example
+    /// ```rust
+    /// # use wutil::ArrayString;
+    /// let mut s = ArrayString::<16>::try_from("α, β").unwrap();
+    ///
+    /// assert!(s.drain(..4).eq(['α', ',', ' ']));
+    /// assert_eq!(s, "β");
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = resolve_range(range, self.len());
+        assert!(
+            self.is_char_boundary(range.start) && self.is_char_boundary(range.end),
+            "range bounds are not char boundaries"
+        );
+
+        Drain {
+            bytes: self.bytes.drain(range),
+        }
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for ArrayString<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> Borrow<str> for ArrayString<N> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> BorrowMut<str> for ArrayString<N> {
+    fn borrow_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl<const N: usize> Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Write for ArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> Hash for ArrayString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<const N: usize> PartialEq<str> for ArrayString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a str> for ArrayString<N> {
+    type Error = CapacityError<&'a str>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let mut string = Self::new();
+        string.try_push_str(s)?;
+
+        Ok(string)
+    }
+}
+
+/// Collects `char`s until one doesn't fit. The rest of the iterator is ignored.
+impl<const N: usize> FromIterator<char> for ArrayString<N> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut string = Self::new();
+        for c in iter {
+            if string.try_push(c).is_err() {
+                break;
+            }
+        }
+
+        string
+    }
+}
+
+/// Collects strings until one doesn't fit. The rest of the iterator is ignored.
+impl<'a, const N: usize> FromIterator<&'a str> for ArrayString<N> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut string = Self::new();
+        for s in iter {
+            if string.try_push_str(s).is_err() {
+                break;
+            }
+        }
+
+        string
+    }
+}
+
+/// Panics if the `char`s don't fit.
+impl<const N: usize> Extend<char> for ArrayString<N> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        for c in iter {
+            self.push(c);
+        }
+    }
+}
+
+/// Panics if the strings don't fit.
+impl<'a, const N: usize> Extend<&'a str> for ArrayString<N> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_str(s);
+        }
+    }
+}
+
+/// An iterator that removes a range of `char`s from an [`ArrayString`].
+///
+/// This `struct` is created by [`ArrayString::drain`].
+pub struct Drain<'a, const N: usize> {
+    bytes: array_vec::Drain<'a, u8, N>,
+}
+
+impl<const N: usize> Drain<'_, N> {
+    /// Gets the part of the range that hasn't been yielded yet.
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+}
+
+impl<const N: usize> Iterator for Drain<'_, N> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.as_str().chars().next()?;
+        self.bytes.nth(c.len_utf8() - 1);
+
+        Some(c)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_str().chars().size_hint()
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Drain<'_, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.nth_back(c.len_utf8() - 1);
+
+        Some(c)
+    }
+}
+
+impl<const N: usize> FusedIterator for Drain<'_, N> {}
+
+impl<const N: usize> Debug for Drain<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_multibyte() {
+        let mut s = ArrayString::<8>::new();
+
+        s.push('é');
+        s.push_str("ab");
+        s.insert(2, 'ß');
+        assert_eq!(s, "éßab");
+
+        assert_eq!(s.remove(0), 'é');
+        assert_eq!(s.pop(), Some('b'));
+        assert_eq!(s, "ßa");
+
+        assert_eq!(
+            s.try_push_str("abcdef").map_err(CapacityError::into_inner),
+            Err("abcdef")
+        );
+        assert_eq!(
+            s.try_insert(0, '€').map_err(CapacityError::into_inner),
+            Ok(())
+        );
+        assert_eq!(s, "€ßa");
+        assert!(s.try_push('€').is_err());
+        assert_eq!(s, "€ßa");
+    }
+
+    #[test]
+    fn collect_truncates() {
+        let s: ArrayString<4> = "abcdef".chars().collect();
+        assert_eq!(s, "abcd");
+
+        let s: ArrayString<4> = ["ab", "cde", "f"].into_iter().collect();
+        assert_eq!(s, "ab");
+    }
+}
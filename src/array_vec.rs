@@ -0,0 +1,754 @@
+//! Contains [`ArrayVec`], a vector with a fixed capacity that doesn't need an allocator.
+
+use core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds},
+    ptr::{self, addr_of},
+};
+
+/// A vector that stores up to `N` items inline.
+/// # Example
+/// ```rust
+/// # use wutil::ArrayVec;
+/// let mut stack = ArrayVec::<u8, 4>::new();
+///
+/// stack.push(1);
+/// stack.extend([2, 3]);
+///
+/// assert_eq!(stack.pop(), Some(3));
+/// assert_eq!(&*stack, &[1, 2]);
+/// assert!(stack.try_push(4).is_ok());
+/// ```
+pub struct ArrayVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    /// Creates an empty vector.
+    pub const fn new() -> Self {
+        Self {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Collects an iterator into an `ArrayVec`. Returns an error containing the first item that
+    /// doesn't fit if the iterator has more than `N` items.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Self::new();
+        for item in iter {
+            vec.try_push(item)?;
+        }
+
+        Ok(vec)
+    }
+
+    /// Adds an item to the end of the vector.
+    /// # Safety
+    /// The vector must not be full.
+    pub(crate) unsafe fn push_unchecked(&mut self, item: T) {
+        unsafe { self.items.get_unchecked_mut(self.len) }.write(item);
+        self.len += 1;
+    }
+
+    /// Fills the vector from `iter`. Returns `false` if `iter` ran out first.
+    pub(crate) fn fill_from<I>(&mut self, iter: &mut I) -> bool
+    where
+        I: Iterator<Item = T> + ?Sized,
+    {
+        while self.len < N {
+            let Some(item) = iter.next() else {
+                return false;
+            };
+
+            unsafe { self.push_unchecked(item) };
+        }
+
+        true
+    }
+
+    /// Gets the number of items in the vector.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector has no items.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the vector has `N` items.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Gets the maximum number of items the vector can hold, which is always `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Gets the number of items that can still be added to the vector.
+    pub const fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Gets a pointer to the vector's buffer, including the uninitialized part.
+    pub const fn as_ptr(&self) -> *const T {
+        self.items.as_ptr().cast()
+    }
+
+    /// Gets a mutable pointer to the vector's buffer, including the uninitialized part.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.items.as_mut_ptr().cast()
+    }
+
+    /// Gets the items of the vector as a slice.
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Gets the items of the vector as a mutable slice.
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    /// Converts this into an array if it is full. Otherwise returns itself.
+    pub fn into_array(self) -> Result<[T; N], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { addr_of!(this.items).cast::<[T; N]>().read() })
+    }
+
+    /// Adds an item to the end of the vector.
+    /// # Panics
+    /// Panics if the vector is full.
+    pub fn push(&mut self, item: T) {
+        assert!(!self.is_full(), "ArrayVec is full");
+        unsafe { self.push_unchecked(item) };
+    }
+
+    /// Adds an item to the end of the vector. Returns the item back if the vector is full.
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+
+        unsafe { self.push_unchecked(item) };
+        Ok(())
+    }
+
+    /// Removes the last item of the vector.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Inserts an item at `index`, shifting the items after it to the right.
+    /// # Panics
+    /// Panics if `index > len` or if the vector is full.
+    pub fn insert(&mut self, index: usize, item: T) {
+        assert!(!self.is_full(), "ArrayVec is full");
+        let _ = self.try_insert(index, item);
+    }
+
+    /// Inserts an item at `index`, shifting the items after it to the right. Returns the item back
+    /// if the vector is full.
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), CapacityError<T>> {
+        let len = self.len;
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+
+        unsafe {
+            let ptr = self.as_mut_ptr().add(index);
+            ptr::copy(ptr, ptr.add(1), len - index);
+            ptr.write(item);
+        }
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Removes the item at `index`, shifting the items after it to the left.
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        assert!(
+            index < len,
+            "removal index (is {index}) should be < len (is {len})"
+        );
+
+        self.len -= 1;
+        unsafe {
+            let ptr = self.as_mut_ptr().add(index);
+            let item = ptr.read();
+            ptr::copy(ptr.add(1), ptr, len - index - 1);
+
+            item
+        }
+    }
+
+    /// Removes the item at `index`, replacing it with the last item.
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        assert!(
+            index < len,
+            "swap_remove index (is {index}) should be < len (is {len})"
+        );
+
+        self.swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// Shortens the vector to `len` items, dropping the rest. Does nothing if the vector is already
+    /// shorter.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let tail =
+            ptr::slice_from_raw_parts_mut(unsafe { self.as_mut_ptr().add(len) }, self.len - len);
+        // Shorten the vector first so a panicking drop can't cause items to be dropped twice
+        self.len = len;
+
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    /// Removes and drops every item of the vector.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Removes the items in `range` and returns an iterator over them. The items after `range` are
+    /// shifted over when the iterator is dropped.
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    /// # Example
+    /// ```rust
+    /// # use wutil::ArrayVec;
+    /// let mut vec = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// assert!(vec.drain(1..3).eq([2, 3]));
+    /// assert_eq!(&*vec, &[1, 4]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = resolve_range(range, self.len);
+        Drain::new(self, range)
+    }
+
+    /// Appends the items of `slice` to the vector. Returns `slice` back without adding any of it
+    /// if there isn't enough room.
+    pub fn try_extend_from_slice<'a>(
+        &mut self,
+        slice: &'a [T],
+    ) -> Result<(), CapacityError<&'a [T]>>
+    where
+        T: Clone,
+    {
+        if slice.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(slice));
+        }
+
+        for item in slice {
+            unsafe { self.push_unchecked(item.clone()) };
+        }
+
+        Ok(())
+    }
+
+    /// Appends the items of `slice` to the vector.
+    /// # Panics
+    /// Panics if there isn't enough room.
+    pub fn extend_from_slice(&mut self, slice: &[T])
+    where
+        T: Clone,
+    {
+        assert!(
+            self.try_extend_from_slice(slice).is_ok(),
+            "ArrayVec is full"
+        );
+    }
+}
+
+/// Converts `range` into indices of a collection with `len` items.
+pub(crate) fn resolve_range<R>(range: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflowed"),
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflowed"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(start <= end, "range starts at {start} but ends at {end}");
+    assert!(
+        end <= len,
+        "range end (is {end}) should be <= len (is {len})"
+    );
+
+    start..end
+}
+
+impl<T, const N: usize> Drop for ArrayVec<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) };
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArrayVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for ArrayVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for ArrayVec<T, N> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> BorrowMut<[T]> for ArrayVec<T, N> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T]> for ArrayVec<T, N> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for ArrayVec<T, N> {
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVec<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for ArrayVec<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for ArrayVec<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for ArrayVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayVec<T, N> {
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+
+        Self {
+            items: unsafe { addr_of!(array).cast::<[MaybeUninit<T>; N]>().read() },
+            len: N,
+        }
+    }
+}
+
+/// Collects up to `N` items. The rest of the iterator is ignored; use [`ArrayVec::try_from_iter`]
+/// to get an error instead.
+impl<T, const N: usize> FromIterator<T> for ArrayVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.fill_from(&mut iter.into_iter());
+
+        vec
+    }
+}
+
+/// Panics if the iterator has more items than fit.
+impl<T, const N: usize> Extend<T> for ArrayVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// Panics if the iterator has more items than fit.
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for ArrayVec<T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            start: 0,
+            vec: self,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the items of an [`ArrayVec`].
+pub struct IntoIter<T, const N: usize> {
+    /// The vector whose items in `start..` have not been yielded. The vector's drop glue is never
+    /// run; the remaining items are dropped by `IntoIter`.
+    vec: ArrayVec<T, N>,
+    start: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Gets the items that haven't been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        // The vector's own slice would include the items that were already moved out
+        unsafe {
+            core::slice::from_raw_parts(
+                self.vec.as_ptr().add(self.start),
+                self.vec.len - self.start,
+            )
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.vec.len {
+            return None;
+        }
+
+        self.start += 1;
+        Some(unsafe { self.vec.items[self.start - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.vec.len - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.vec.len {
+            return None;
+        }
+
+        self.vec.pop()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let remaining = ptr::slice_from_raw_parts_mut(
+            unsafe { self.vec.as_mut_ptr().add(self.start) },
+            self.vec.len - self.start,
+        );
+        // The vector must not drop the items that were already yielded
+        self.vec.len = 0;
+
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+/// An iterator that removes a range of items from an [`ArrayVec`].
+///
+/// This `struct` is created by [`ArrayVec::drain`].
+pub struct Drain<'a, T, const N: usize> {
+    /// The vector, whose length is set to the start of the drained range until `Drain` is dropped
+    vec: &'a mut ArrayVec<T, N>,
+    /// The drained items that haven't been yielded yet
+    remaining: Range<usize>,
+    /// The items after the drained range
+    tail: Range<usize>,
+}
+
+impl<'a, T, const N: usize> Drain<'a, T, N> {
+    fn new(vec: &'a mut ArrayVec<T, N>, range: Range<usize>) -> Self {
+        let tail = range.end..vec.len;
+        // If `Drain` is leaked, the drained items and the tail are leaked along with it
+        vec.len = range.start;
+
+        Self {
+            vec,
+            remaining: range,
+            tail,
+        }
+    }
+
+    /// Gets the items that haven't been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            core::slice::from_raw_parts(
+                self.vec.as_ptr().add(self.remaining.start),
+                self.remaining.len(),
+            )
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.remaining.next()?;
+        Some(unsafe { self.vec.items[index].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Drain<'_, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.remaining.next_back()?;
+        Some(unsafe { self.vec.items[index].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for Drain<'_, T, N> {}
+
+impl<T: Debug, const N: usize> Debug for Drain<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Drop for Drain<'_, T, N> {
+    fn drop(&mut self) {
+        let remaining = ptr::slice_from_raw_parts_mut(
+            unsafe { self.vec.as_mut_ptr().add(self.remaining.start) },
+            self.remaining.len(),
+        );
+        self.remaining = 0..0;
+
+        unsafe {
+            // If dropping an item panics, the tail is leaked
+            ptr::drop_in_place(remaining);
+
+            let start = self.vec.len;
+            let ptr = self.vec.as_mut_ptr();
+            ptr::copy(ptr.add(self.tail.start), ptr.add(start), self.tail.len());
+        }
+
+        self.vec.len += self.tail.len();
+    }
+}
+
+/// An error returned when an [`ArrayVec`] or [`ArrayString`](crate::ArrayString) doesn't have
+/// enough room. Contains what couldn't be added.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    item: T,
+}
+
+impl<T> CapacityError<T> {
+    pub(crate) const fn new(item: T) -> Self {
+        Self { item }
+    }
+
+    /// Gets what couldn't be added.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T> Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError")
+    }
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::test_utils::DropCounter;
+
+    #[test]
+    fn insert_remove() {
+        let mut vec = ArrayVec::<u8, 4>::new();
+
+        vec.insert(0, 2);
+        vec.insert(0, 1);
+        vec.insert(2, 4);
+        vec.insert(2, 3);
+
+        assert_eq!(vec, [1, 2, 3, 4]);
+        assert_eq!(
+            vec.try_insert(1, 5).map_err(CapacityError::into_inner),
+            Err(5)
+        );
+        assert_eq!(vec.remove(1), 2);
+        assert_eq!(vec.swap_remove(0), 1);
+        assert_eq!(vec, [4, 3]);
+    }
+
+    #[test]
+    fn drain_drops_and_shifts() {
+        let drops = Cell::new(0);
+        let mut vec: ArrayVec<_, 5> = (0..5).map(|_| DropCounter(&drops)).collect();
+
+        let mut drain = vec.drain(1..4);
+        drop(drain.next());
+        drop(drain.next_back());
+        assert_eq!(drops.get(), 2);
+
+        drop(drain);
+        assert_eq!(drops.get(), 3);
+        assert_eq!(vec.len(), 2);
+
+        drop(vec);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn into_iter_drops_remaining() {
+        let drops = Cell::new(0);
+        let vec: ArrayVec<_, 4> = (0..3).map(|_| DropCounter(&drops)).collect();
+
+        let mut items = vec.into_iter();
+        drop(items.next());
+        drop(items.next_back());
+        assert_eq!(drops.get(), 2);
+
+        drop(items);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn collect_overflow() {
+        let truncated: ArrayVec<u8, 2> = (1..=3).collect();
+        assert_eq!(truncated, [1, 2]);
+
+        let err = ArrayVec::<u8, 2>::try_from_iter(1..=3).unwrap_err();
+        assert_eq!(err.into_inner(), 3);
+    }
+}
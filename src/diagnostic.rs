@@ -0,0 +1,582 @@
+//! Contains a compiler-style diagnostic type and a renderer that prints annotated source snippets.
+
+use core::fmt::{self, Display, Write};
+
+use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+
+use crate::{source_map::SourceFile, span::Span};
+
+/// How severe a [`Diagnostic`] is.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+/// A message with labelled spans that can be rendered by a [`Renderer`].
+///
+/// The first label is the primary label. It is underlined with `^` and decides which location is
+/// printed in the header. Every other label is underlined with `-`.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<(Span, String)>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Adds a label to the diagnostic. The first label added is the primary label.
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push((span, message.into()));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Renders the diagnostic with the default [`Renderer`].
+    pub fn render(&self, file: &SourceFile, out: &mut impl Write) -> fmt::Result {
+        Renderer::new().render(self, file, out)
+    }
+}
+
+/// Writes rustc-style snippets for [`Diagnostic`]s.
+/// # Example
+/// ```rust
+/// # use wutil::diagnostic::Diagnostic;
+/// # use wutil::source_map::SourceFile;
+/// # use wutil::Span;
+/// let file = SourceFile::new("main.rs", "fn main() {\n    let x: u32 = \"hi\";\n}\n");
+///
+/// let diagnostic = Diagnostic::error("mismatched types")
+///     .with_label(Span::from(29..33), "expected `u32`, found `&str`")
+///     .with_label(Span::from(23..26), "expected due to this");
+///
+/// let mut out = String::new();
+/// diagnostic.render(&file, &mut out).unwrap();
+///
+/// let expected = "\
+/// error: mismatched types
+///  --> main.rs:2:18
+///   |
+/// 2 |     let x: u32 = \"hi\";
+///   |            ---   ^^^^ expected `u32`, found `&str`
+///   |            |
+///   |            expected due to this
+/// ";
+///
+/// assert_eq!(out, expected);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Renderer {
+    tab_width: usize,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self { tab_width: 4 }
+    }
+}
+
+/// A label resolved to lines and display columns.
+struct Label<'a> {
+    primary: bool,
+    message: &'a str,
+    start: (usize, usize),
+    end: (usize, usize),
+    /// Whether a multi-line label starts at the first non-whitespace character of its line.
+    starts_line: bool,
+}
+
+impl Label<'_> {
+    fn is_multiline(&self) -> bool {
+        self.start.0 != self.end.0
+    }
+
+    fn underline_char(&self) -> char {
+        if self.primary { '^' } else { '-' }
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many columns a tab character is expanded to.
+    pub fn with_tab_width(self, tab_width: usize) -> Self {
+        Self { tab_width }
+    }
+
+    /// Writes `diagnostic` to `out`. Labels whose spans cannot be resolved in `file` are skipped.
+    pub fn render(
+        &self,
+        diagnostic: &Diagnostic,
+        file: &SourceFile,
+        out: &mut impl Write,
+    ) -> fmt::Result {
+        writeln!(out, "{}: {}", diagnostic.severity, diagnostic.message)?;
+
+        let labels: Vec<Label> = diagnostic
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (span, message))| self.resolve_label(file, *span, message, i == 0))
+            .collect();
+
+        let mut shown_lines = BTreeSet::new();
+        for label in &labels {
+            shown_lines.insert(label.start.0);
+            shown_lines.insert(label.end.0);
+
+            if label.is_multiline() {
+                shown_lines.insert(label.start.0 + 1);
+                shown_lines.insert(label.end.0 - 1);
+            }
+        }
+
+        let gutter = shown_lines.last().map_or(0, |l| digits(l + 1));
+
+        if let Some((span, _)) = diagnostic.labels.first()
+            && let Some(pos) = file.position(span.start)
+        {
+            writeln!(out, "{:gutter$}--> {}:{}", "", file.name(), pos)?;
+        }
+
+        if !labels.is_empty() {
+            writeln!(out, "{:gutter$} |", "")?;
+        }
+
+        let mut multiline: Vec<&Label> = labels.iter().filter(|l| l.is_multiline()).collect();
+        multiline.sort_by_key(|l| (l.start, core::cmp::Reverse(l.end)));
+
+        let margin = multiline.len() * 2;
+        let mut open = vec![false; multiline.len()];
+        let mut prev_line = None;
+
+        for &line in &shown_lines {
+            if prev_line.is_some_and(|prev| prev + 1 != line) {
+                writeln!(out, "...")?;
+            }
+            prev_line = Some(line);
+
+            for (depth, label) in multiline.iter().enumerate() {
+                if label.start.0 == line && label.starts_line {
+                    open[depth] = true;
+                }
+            }
+
+            let mut row = margin_row(&open, margin);
+            for (depth, label) in multiline.iter().enumerate() {
+                if label.start.0 == line && label.starts_line {
+                    row[depth * 2] = '/';
+                }
+            }
+
+            let line_span = file.line_span(line).unwrap();
+            row.extend(self.expand_tabs(&file.text()[line_span]));
+            write_row(out, &format_args!("{:>gutter$}", line + 1), &row)?;
+
+            let single: Vec<&Label> = labels
+                .iter()
+                .filter(|l| !l.is_multiline() && l.start.0 == line)
+                .collect();
+
+            for row in single_line_rows(&single, &margin_row(&open, margin)) {
+                write_row(out, &format_args!("{:gutter$}", ""), &row)?;
+            }
+
+            for (depth, label) in multiline.iter().enumerate() {
+                if label.start.0 != line || label.starts_line {
+                    continue;
+                }
+
+                let mut row = margin_row(&open, margin);
+                put(&mut row, depth * 2, ' ');
+                for col in depth * 2 + 1..margin + label.start.1 {
+                    put(&mut row, col, '_');
+                }
+                put(&mut row, margin + label.start.1, label.underline_char());
+
+                write_row(out, &format_args!("{:gutter$}", ""), &row)?;
+                open[depth] = true;
+            }
+
+            for (depth, label) in multiline.iter().enumerate() {
+                if label.end.0 != line {
+                    continue;
+                }
+
+                let caret = margin + label.end.1 - 1;
+
+                let mut row = margin_row(&open, margin);
+                for col in depth * 2 + 1..caret {
+                    put(&mut row, col, '_');
+                }
+                put(&mut row, caret, label.underline_char());
+
+                if !label.message.is_empty() {
+                    row.push(' ');
+                    row.extend(label.message.chars());
+                }
+
+                write_row(out, &format_args!("{:gutter$}", ""), &row)?;
+                open[depth] = false;
+            }
+        }
+
+        if !diagnostic.notes.is_empty() && !labels.is_empty() {
+            writeln!(out, "{:gutter$} |", "")?;
+        }
+
+        for note in &diagnostic.notes {
+            writeln!(out, "{:gutter$} = note: {}", "", note)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_label<'a>(
+        &self,
+        file: &SourceFile,
+        span: Span,
+        message: &'a str,
+        primary: bool,
+    ) -> Option<Label<'a>> {
+        if span.start > span.end {
+            return None;
+        }
+
+        file.position(span.start)?;
+        file.position(span.end)?;
+
+        let start_line = file.line_index(span.start)?;
+        let mut end_line = file.line_index(span.end)?;
+
+        let start_col = self.display_col(file, start_line, span.start);
+        let mut end_col = self.display_col(file, end_line, span.end);
+
+        // A span ending right after a line break is treated as ending on the line break itself.
+        if end_line > start_line && end_col == 0 {
+            end_line -= 1;
+            end_col = self.display_col(file, end_line, span.end - 1) + 1;
+        }
+
+        let line_start = file.line_span(start_line)?.start;
+        let starts_line = file.text()[line_start..span.start].trim().is_empty();
+
+        Some(Label {
+            primary,
+            message,
+            start: (start_line, start_col),
+            end: (end_line, end_col),
+            starts_line,
+        })
+    }
+
+    /// Gets the display column of `byte_index` within `line`. Positions past the end of the
+    /// line's content (ie line terminators) are one column each.
+    fn display_col(&self, file: &SourceFile, line: usize, byte_index: usize) -> usize {
+        let line_span = file.line_span(line).unwrap();
+        let content = &file.text()[line_span];
+
+        let offset = byte_index - line_span.start;
+        let within = offset.min(content.len());
+
+        content[..within]
+            .chars()
+            .map(|c| self.char_width(c))
+            .sum::<usize>()
+            + (offset - within)
+    }
+
+    fn char_width(&self, c: char) -> usize {
+        if c == '\t' {
+            self.tab_width
+        } else {
+            char_width(c)
+        }
+    }
+
+    fn expand_tabs<'a>(&self, text: &'a str) -> impl Iterator<Item = char> + 'a {
+        let tab_width = self.tab_width;
+
+        text.chars().flat_map(move |c| {
+            let (c, n) = if c == '\t' { (' ', tab_width) } else { (c, 1) };
+            core::iter::repeat_n(c, n)
+        })
+    }
+}
+
+/// Builds the rows underneath a source line for labels that start and end on that line.
+fn single_line_rows(labels: &[&Label], margin: &[char]) -> Vec<Vec<char>> {
+    if labels.is_empty() {
+        return Vec::new();
+    }
+
+    let offset = margin.len();
+
+    let mut labels = labels.to_vec();
+    labels.sort_by_key(|l| l.start.1);
+
+    let mut underline = margin.to_vec();
+    for label in labels
+        .iter()
+        .filter(|l| !l.primary)
+        .chain(labels.iter().filter(|l| l.primary))
+    {
+        let end = label.end.1.max(label.start.1 + 1);
+
+        for col in label.start.1..end {
+            put(&mut underline, offset + col, label.underline_char());
+        }
+    }
+
+    let last = labels.last().unwrap();
+    let inline = labels
+        .iter()
+        .all(|l| core::ptr::eq(*l, *last) || l.end.1.max(l.start.1 + 1) <= last.start.1);
+
+    let hanging: Vec<&Label> = labels
+        .iter()
+        .filter(|l| !(l.message.is_empty() || inline && core::ptr::eq(**l, *last)))
+        .copied()
+        .collect();
+
+    if inline && !last.message.is_empty() {
+        underline.push(' ');
+        underline.extend(last.message.chars());
+    }
+
+    let mut rows = vec![underline];
+
+    if hanging.is_empty() {
+        return rows;
+    }
+
+    let mut connectors = margin.to_vec();
+    for label in &hanging {
+        put(&mut connectors, offset + label.start.1, '|');
+    }
+    rows.push(connectors);
+
+    for (i, label) in hanging.iter().enumerate().rev() {
+        let mut row = margin.to_vec();
+
+        for other in &hanging[..i] {
+            put(&mut row, offset + other.start.1, '|');
+        }
+
+        let col = offset + label.start.1;
+        row.truncate(col);
+        row.resize(col, ' ');
+        row.extend(label.message.chars());
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn margin_row(open: &[bool], margin: usize) -> Vec<char> {
+    let mut row = vec![' '; margin];
+
+    for (depth, _) in open.iter().enumerate().filter(|(_, o)| **o) {
+        row[depth * 2] = '|';
+    }
+
+    row
+}
+
+fn put(row: &mut Vec<char>, col: usize, c: char) {
+    if row.len() <= col {
+        row.resize(col + 1, ' ');
+    }
+
+    row[col] = c;
+}
+
+fn write_row(out: &mut impl Write, gutter: &dyn Display, row: &[char]) -> fmt::Result {
+    let len = row.iter().rposition(|c| *c != ' ').map_or(0, |i| i + 1);
+
+    write!(out, "{gutter} |")?;
+
+    if len > 0 {
+        out.write_char(' ')?;
+    }
+
+    for c in &row[..len] {
+        out.write_char(*c)?;
+    }
+
+    out.write_char('\n')
+}
+
+fn digits(mut n: usize) -> usize {
+    let mut digits = 1;
+
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+
+    digits
+}
+
+/// Gets the number of terminal columns taken up by a character.
+#[cfg(feature = "unicode")]
+fn char_width(c: char) -> usize {
+    crate::str::unicode::char_width(c)
+}
+
+/// Approximates the number of terminal columns taken up by a character.
+#[cfg(not(feature = "unicode"))]
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(text: &str, diagnostic: &Diagnostic) -> String {
+        let file = SourceFile::new("test.rs", text);
+
+        let mut out = String::new();
+        diagnostic.render(&file, &mut out).unwrap();
+
+        out
+    }
+
+    #[test]
+    fn multiline_span() {
+        let text = "fn foo() -> u32 {\n    \"hi\"\n}\n";
+        let diagnostic = Diagnostic::error("mismatched types")
+            .with_label(Span::from(16..28), "expected `u32`")
+            .with_note("this is a note");
+
+        let expected = "\
+error: mismatched types
+ --> test.rs:1:17
+  |
+1 |   fn foo() -> u32 {
+  |  _________________^
+2 | |     \"hi\"
+3 | | }
+  | |_^ expected `u32`
+  |
+  = note: this is a note
+";
+
+        assert_eq!(render(text, &diagnostic), expected);
+    }
+
+    #[test]
+    fn multiline_span_at_line_start() {
+        let text = "fn foo() {\n    bar\n}\n";
+        let diagnostic = Diagnostic::warning("unused function").with_label(Span::from(0..20), "");
+
+        let expected = "\
+warning: unused function
+ --> test.rs:1:1
+  |
+1 | / fn foo() {
+2 | |     bar
+3 | | }
+  | |_^
+";
+
+        assert_eq!(render(text, &diagnostic), expected);
+    }
+
+    #[test]
+    fn tabs_and_wide_chars() {
+        let text = "\tlet 名前 = x;";
+        let diagnostic = Diagnostic::error("bad")
+            .with_label(Span::from(5..11), "name")
+            .with_label(Span::from(14..15), "value");
+
+        let expected = "\
+error: bad
+ --> test.rs:1:6
+  |
+1 |     let 名前 = x;
+  |         ^^^^   - value
+  |         |
+  |         name
+";
+
+        assert_eq!(render(text, &diagnostic), expected);
+    }
+
+    #[test]
+    fn elided_lines() {
+        let text = "a\nb\nc\nd\ne\nf\n";
+        let diagnostic = Diagnostic::error("far apart")
+            .with_label(Span::from(0..1), "here")
+            .with_label(Span::from(10..11), "and here");
+
+        let expected = "\
+error: far apart
+ --> test.rs:1:1
+  |
+1 | a
+  | ^ here
+...
+6 | f
+  | - and here
+";
+
+        assert_eq!(render(text, &diagnostic), expected);
+    }
+}
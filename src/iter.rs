@@ -1,14 +1,15 @@
 //! Contains extensions for rust iterators.
 
-use core::{mem::MaybeUninit, ptr::addr_of};
-
+mod partial_array;
 mod split;
 
+pub use partial_array::*;
 pub use split::*;
 
 /// An extension trait for `Iterator`
 pub trait IterExt: Iterator {
     /// Gets `N` items from an iterator and returns them as an array. Otherwise returns `None`.
+    /// Use [`collect_array_partial`](IterExt::collect_array_partial) to keep the items instead.
     /// # Example
     /// ```rust
     /// # use wutil::prelude::*;
@@ -18,20 +19,194 @@ pub trait IterExt: Iterator {
     /// assert_eq!(&nums, &[0, 10, 20, 30, 40]);
     /// ```
     fn collect_n<const N: usize>(&mut self) -> Option<[Self::Item; N]> {
-        let mut arr: [MaybeUninit<Self::Item>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+        self.collect_array_partial().ok()
+    }
+
+    /// Gets `N` items from an iterator and returns them as an array. If the iterator runs out
+    /// first, returns the items it had in an [`ArrayVec`](crate::ArrayVec) instead.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let mut header = [0x7fu8, b'E', b'L'].into_iter();
+    /// let partial = header.collect_array_partial::<4>().unwrap_err();
+    ///
+    /// assert_eq!(&*partial, b"\x7fEL");
+    /// ```
+    fn collect_array_partial<const N: usize>(
+        &mut self,
+    ) -> Result<[Self::Item; N], PartialArray<Self::Item, N>> {
+        let mut items = PartialArray::new();
+        items.fill_from(self);
+
+        items.into_array()
+    }
 
-        for i in 0..N {
-            let Some(item) = self.next() else {
-                for item in &mut arr[0..i] {
-                    unsafe { item.assume_init_drop() };
-                }
-                return None;
-            };
+    /// Collects an iterator with exactly `N` items into an array. Returns an error if the
+    /// iterator has fewer or more items.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::iter::CollectExactError;
+    /// let rgb: [u8; 3] = "12,34,56".split(',').map(|n| n.parse().unwrap()).collect_exact().unwrap();
+    /// assert_eq!(rgb, [12, 34, 56]);
+    ///
+    /// let rgba = "12,34,56,78".split(',').collect_exact::<3>();
+    /// assert!(matches!(rgba, Err(CollectExactError::TooMany(["12", "34", "56"], "78"))));
+    /// ```
+    fn collect_exact<const N: usize>(
+        &mut self,
+    ) -> Result<[Self::Item; N], CollectExactError<Self::Item, N>> {
+        let items = self
+            .collect_array_partial()
+            .map_err(CollectExactError::TooFew)?;
 
-            arr[i].write(item);
+        match self.next() {
+            Some(next) => Err(CollectExactError::TooMany(items, next)),
+            None => Ok(items),
         }
+    }
+
+    /// Gets `N` items from an iterator of `Result`s and returns them as an array. Stops at the
+    /// first error.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::iter::TryCollectError;
+    /// let nums: [u8; 2] = "1 2 x".split(' ').map(str::parse).try_collect_n().unwrap();
+    /// assert_eq!(nums, [1, 2]);
+    ///
+    /// let nums = "1 x 3".split(' ').map(str::parse::<u8>).try_collect_n::<_, _, 3>();
+    /// let Err(TryCollectError::Err(parsed, _)) = nums else { panic!() };
+    ///
+    /// assert_eq!(&*parsed, &[1]);
+    /// ```
+    fn try_collect_n<T, E, const N: usize>(&mut self) -> Result<[T; N], TryCollectError<T, E, N>>
+    where
+        Self: Iterator<Item = Result<T, E>>,
+    {
+        let mut items = PartialArray::new();
+        let mut error = None;
+
+        items.fill_from(&mut self.map_while(|item| item.map_err(|err| error = Some(err)).ok()));
+
+        match error {
+            Some(err) => Err(TryCollectError::Err(items, err)),
+            None => items.into_array().map_err(TryCollectError::TooFew),
+        }
+    }
+
+    /// Returns an iterator over `N` items of the iterator at a time. The items left over at the end
+    /// can be gotten with [`ArrayChunks::remainder`].
+    ///
+    /// This isn't named `array_chunks` to avoid colliding with the unstable
+    /// [`Iterator::array_chunks`].
+    /// # Panics
+    /// Panics if `N` is 0.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let mut pairs = [1, 2, 3, 4, 5].into_iter().array_chunks_exact::<2>();
+    ///
+    /// assert_eq!(pairs.by_ref().collect::<Vec<_>>(), [[1, 2], [3, 4]]);
+    /// assert_eq!(pairs.remainder(), &[5]);
+    /// ```
+    fn array_chunks_exact<const N: usize>(self) -> ArrayChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        ArrayChunks::new(self)
+    }
 
-        Some(unsafe { addr_of!(arr).cast::<[Self::Item; N]>().read() })
+    /// Splits an iterator into groups of items separated by items matching `pred`. Unlike
+    /// [`IterCloneExt::split`], this walks the iterator only once, so it works on iterators that
+    /// can't be cloned.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let bytes = b"GET /\nHost: a\n\nbody".iter().copied();
+    ///
+    /// let lines: Vec<Vec<u8>> = bytes.split_buffered(|b| *b == b'\n').collect();
+    /// let expected: &[&[u8]] = &[b"GET /", b"Host: a", b"", b"body"];
+    ///
+    /// assert_eq!(lines, expected);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn split_buffered<P>(self, pred: P) -> SplitBuffered<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        SplitBuffered::new(self, pred)
+    }
+
+    /// Like [`split_buffered`](IterExt::split_buffered), but includes the separator at the end of
+    /// each group.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let nums = [0u32, 10, 20, 0, 0, 5, 50, 0];
+    ///
+    /// let split_nums: Vec<Vec<u32>> = nums
+    ///     .iter()
+    ///     .copied()
+    ///     .filter(|n| n % 2 == 0)
+    ///     .split_inclusive_buffered(|n| *n == 0)
+    ///     .collect();
+    ///
+    /// let expected: &[&[u32]] = &[&[0], &[10, 20, 0], &[0], &[50, 0], &[]];
+    ///
+    /// assert_eq!(split_nums, expected);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn split_inclusive_buffered<P>(self, pred: P) -> SplitInclusiveBuffered<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        SplitInclusiveBuffered::new(self, pred)
+    }
+
+    /// Splits an iterator into groups of items separated by the sequence `delimiter`. Like
+    /// [`split_buffered`](IterExt::split_buffered), this walks the iterator only once. Empty
+    /// delimiters match between every item, like [`SliceExt::split_seq`](crate::slice::SliceExt::split_seq).
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let stream = b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".iter().copied();
+    ///
+    /// let lines: Vec<Vec<u8>> = stream.split_on_seq(b"\r\n").collect();
+    /// let expected: &[&[u8]] = &[b"HTTP/1.1 200 OK", b"Content-Length: 0", b"", b""];
+    ///
+    /// assert_eq!(lines, expected);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn split_on_seq(self, delimiter: &[Self::Item]) -> SplitOnSeq<'_, Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        SplitOnSeq::new(self, delimiter)
+    }
+
+    /// Like [`split_on_seq`](IterExt::split_on_seq), but includes the delimiter at the end of
+    /// each group.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let stream = b"a\r\nb".iter().copied();
+    ///
+    /// let lines: Vec<Vec<u8>> = stream.split_on_seq_inclusive(b"\r\n").collect();
+    /// let expected: &[&[u8]] = &[b"a\r\n", b"b"];
+    ///
+    /// assert_eq!(lines, expected);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn split_on_seq_inclusive(self, delimiter: &[Self::Item]) -> SplitOnSeqInclusive<'_, Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        SplitOnSeqInclusive::new(self, delimiter)
     }
 }
 
@@ -83,6 +258,116 @@ pub trait IterCloneExt: Iterator + Clone {
     {
         SplitInclusive::new(self, pred)
     }
+    /// Like [`split`](IterCloneExt::split), but a separator at the end of the iterator doesn't
+    /// produce a trailing empty group.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let nums = [1u32, 0, 2, 3, 0];
+    ///
+    /// let split_nums: Vec<Vec<u32>> = nums
+    ///     .iter()
+    ///     .split_terminator(|n| **n == 0)
+    ///     .map(|n| n.copied().collect::<Vec<u32>>())
+    ///     .collect();
+    ///
+    /// let expected: &[&[u32]] = &[&[1], &[2, 3]];
+    ///
+    /// assert_eq!(split_nums, expected);
+    /// ```
+    fn split_terminator<P>(self, pred: P) -> SplitTerminator<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool + Clone,
+    {
+        SplitTerminator::new(self, pred)
+    }
+    /// Like [`split`](IterCloneExt::split), but returns at most `n` groups. The last group contains
+    /// the rest of the iterator, including any separators.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let nums = [1u32, 0, 2, 0, 3];
+    ///
+    /// let split_nums: Vec<Vec<u32>> = nums
+    ///     .iter()
+    ///     .splitn(2, |n| **n == 0)
+    ///     .map(|n| n.copied().collect::<Vec<u32>>())
+    ///     .collect();
+    ///
+    /// let expected: &[&[u32]] = &[&[1], &[2, 0, 3]];
+    ///
+    /// assert_eq!(split_nums, expected);
+    /// ```
+    fn splitn<P>(self, n: usize, pred: P) -> SplitN<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool + Clone,
+    {
+        SplitN::new(self, n, pred)
+    }
+    /// Splits an iterator into an iterator of iterators. Includes the separator at the start of
+    /// each group except the first.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let nums = [0u32, 10, 20, 0, 0, 5, 50, 0];
+    ///
+    /// let split_nums: Vec<Vec<u32>> = nums
+    ///     .iter()
+    ///     .filter(|n| *n % 2 == 0)
+    ///     .split_initiator(|n| **n == 0)
+    ///     .map(|n| n.copied().collect::<Vec<u32>>())
+    ///     .collect();
+    ///
+    /// let expected: &[&[u32]] = &[&[], &[0, 10, 20], &[0], &[0, 50], &[0]];
+    ///
+    /// assert_eq!(split_nums, expected);
+    /// ```
+    fn split_initiator<P>(self, pred: P) -> SplitInitiator<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool + Clone,
+    {
+        SplitInitiator::new(self, pred)
+    }
+    /// Like [`split`](IterCloneExt::split), but skips empty groups, like
+    /// [`str::split_whitespace`].
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let words: Vec<String> = "  two   words "
+    ///     .chars()
+    ///     .split_nonempty(|c| *c == ' ')
+    ///     .map(|word| word.collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(words, ["two", "words"]);
+    /// ```
+    fn split_nonempty<P>(self, pred: P) -> SplitNonEmpty<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool + Clone,
+    {
+        SplitNonEmpty::new(self, pred)
+    }
+    /// Like [`split`](IterCloneExt::split), but yields the groups from back to front. Each group
+    /// still yields its items from front to back.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let file_name: String = "/usr/lib/libfoo.so"
+    ///     .chars()
+    ///     .rsplit(|c| *c == '/')
+    ///     .next()
+    ///     .unwrap()
+    ///     .collect();
+    ///
+    /// assert_eq!(file_name, "libfoo.so");
+    /// ```
+    fn rsplit<P>(self, pred: P) -> RSplit<Self, P>
+    where
+        Self: DoubleEndedIterator,
+        P: FnMut(&Self::Item) -> bool + Clone,
+    {
+        RSplit::new(self, pred)
+    }
 }
 
 impl<I: Iterator> IterExt for I {}
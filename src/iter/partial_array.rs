@@ -0,0 +1,209 @@
+use core::{
+    fmt::{self, Debug, Display},
+    iter::FusedIterator,
+};
+
+use super::IterExt;
+use crate::array_vec::ArrayVec;
+
+/// The items collected before an iterator ran out, returned when it runs out before filling an
+/// array.
+///
+/// Dropping it drops its items, so no items are lost or leaked if a collecting iterator panics.
+/// # Example
+/// ```rust
+/// # use wutil::prelude::*;
+/// let partial = [1, 2].into_iter().collect_array_partial::<3>().unwrap_err();
+///
+/// assert_eq!(partial.len(), 2);
+/// assert_eq!(&*partial, &[1, 2]);
+/// ```
+pub type PartialArray<T, const N: usize> = ArrayVec<T, N>;
+
+/// An error returned by [`IterExt::collect_exact`](super::IterExt::collect_exact).
+pub enum CollectExactError<T, const N: usize> {
+    /// The iterator had fewer than `N` items.
+    TooFew(PartialArray<T, N>),
+    /// The iterator had more than `N` items. Contains the first `N` items and the one after them.
+    TooMany([T; N], T),
+}
+
+impl<T: Debug, const N: usize> Debug for CollectExactError<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew(items) => f.debug_tuple("TooFew").field(items).finish(),
+            Self::TooMany(items, next) => {
+                f.debug_tuple("TooMany").field(items).field(next).finish()
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Display for CollectExactError<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew(items) => write!(f, "expected {N} items, got {}", items.len()),
+            Self::TooMany(..) => write!(f, "expected {N} items, got more"),
+        }
+    }
+}
+
+impl<T: Debug, const N: usize> core::error::Error for CollectExactError<T, N> {}
+
+/// An error returned by [`IterExt::try_collect_n`](super::IterExt::try_collect_n).
+pub enum TryCollectError<T, E, const N: usize> {
+    /// The iterator had fewer than `N` items.
+    TooFew(PartialArray<T, N>),
+    /// The iterator returned an error. Contains the items before the error.
+    Err(PartialArray<T, N>, E),
+}
+
+impl<T: Debug, E: Debug, const N: usize> Debug for TryCollectError<T, E, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew(items) => f.debug_tuple("TooFew").field(items).finish(),
+            Self::Err(items, err) => f.debug_tuple("Err").field(items).field(err).finish(),
+        }
+    }
+}
+
+impl<T, E: Display, const N: usize> Display for TryCollectError<T, E, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew(items) => write!(f, "expected {N} items, got {}", items.len()),
+            Self::Err(_, err) => err.fmt(f),
+        }
+    }
+}
+
+impl<T: Debug, E: core::error::Error + 'static, const N: usize> core::error::Error
+    for TryCollectError<T, E, N>
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::TooFew(_) => None,
+            Self::Err(_, err) => Some(err),
+        }
+    }
+}
+
+/// Iterator returned by [`IterExt::array_chunks_exact`](super::IterExt::array_chunks_exact)
+pub struct ArrayChunks<I, const N: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    /// The items left over after the last full chunk, once the iterator has run out
+    remainder: Option<PartialArray<I::Item, N>>,
+}
+
+impl<I, const N: usize> ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    pub(super) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+
+        Self {
+            iter,
+            remainder: None,
+        }
+    }
+
+    /// Gets the items left over after the last full chunk. This is empty until the iterator has
+    /// run out.
+    pub fn remainder(&self) -> &[I::Item] {
+        self.remainder.as_deref().unwrap_or(&[])
+    }
+
+    /// Takes the items left over after the last full chunk.
+    pub fn into_remainder(self) -> PartialArray<I::Item, N> {
+        self.remainder.unwrap_or_default()
+    }
+}
+
+impl<I, const N: usize> Iterator for ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remainder.is_some() {
+            return None;
+        }
+
+        match self.iter.collect_array_partial() {
+            Ok(chunk) => Some(chunk),
+            Err(remainder) => {
+                self.remainder = Some(remainder);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remainder.is_some() {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        (lower / N, upper.map(|upper| upper / N))
+    }
+}
+
+impl<I, const N: usize> FusedIterator for ArrayChunks<I, N> where I: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, panic::AssertUnwindSafe};
+
+    use super::*;
+    use crate::test_utils::DropCounter;
+
+    #[test]
+    fn drops_remaining_items() {
+        let drops = Cell::new(0);
+
+        let mut iter = (0..3).map(|_| DropCounter(&drops));
+        let partial = iter.collect_array_partial::<4>().unwrap_err();
+        let mut items = partial.into_iter();
+
+        drop(items.next());
+        assert_eq!(drops.get(), 1);
+
+        drop(items);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn panic_safety() {
+        extern crate std;
+
+        let drops = Cell::new(0);
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut iter = (0..4).map(|i| {
+                assert!(i != 2);
+                DropCounter(&drops)
+            });
+
+            iter.collect_n::<4>()
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn chunks_remainder() {
+        let mut chunks = (1..=7).array_chunks_exact::<3>();
+
+        assert_eq!(chunks.next(), Some([1, 2, 3]));
+        assert_eq!(chunks.next(), Some([4, 5, 6]));
+        assert_eq!(chunks.remainder(), &[]);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), &[7]);
+        assert_eq!(chunks.next(), None);
+    }
+}
@@ -1,4 +1,14 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+
 /// Iterator returned by [`Iterator::split`](crate::prelude::IterCloneExt::split)
+///
+/// This is double-ended if the underlying iterator is. Groups taken from the back still yield
+/// their items from front to back. To find its first item, a group either skips the items before
+/// it once with [`Iterator::nth`] or walks back from its end with
+/// [`DoubleEndedIterator::nth_back`] for every item, whichever takes fewer steps.
 pub struct Split<I, F>
 where
     I: Iterator + Clone,
@@ -6,6 +16,8 @@ where
 {
     iter: Option<I>,
     pred: F,
+    /// The number of items left in `iter`, once a group has been taken from the back
+    len: Option<usize>,
 }
 
 impl<I, F> Split<I, F>
@@ -17,6 +29,7 @@ where
         Self {
             iter: Some(iter),
             pred,
+            len: None,
         }
     }
 }
@@ -28,7 +41,62 @@ where
     F: FnMut(&I::Item) -> bool + Clone,
 {
     iter: Option<I>,
-    pred: F,
+    /// The predicate that ends the group, or `None` if the group is the rest of the iterator
+    pred: Option<F>,
+    /// Where the group is in `iter`, if it was taken from the back of a [`Split`]
+    back: Option<BackGroup<I>>,
+}
+
+/// A group taken from the back of a [`Split`]. Its iterator ends where the group does, so the
+/// group is found by counting.
+struct BackGroup<I>
+where
+    I: Iterator,
+{
+    /// The number of items before the group that haven't been skipped
+    before: usize,
+    /// The number of items left in the group
+    len: usize,
+    /// Gets the item `n` places from the back of a copy of an iterator
+    nth_back: fn(&I, usize) -> Option<I::Item>,
+}
+
+impl<I, F> SplitIterator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    fn new(iter: I, pred: &F) -> Self {
+        Self {
+            iter: Some(iter),
+            pred: Some(pred.clone()),
+            back: None,
+        }
+    }
+
+    /// Gets the next item of a group that was taken from the back.
+    fn next_counted(iter: &mut I, group: &mut BackGroup<I>) -> Option<I::Item> {
+        if group.len == 0 {
+            return None;
+        }
+
+        // Skipping the items before the group costs `before` steps once, while walking back costs
+        // the number of items left in the group for every item
+        let walk_back_steps = group.len.saturating_mul(group.len + 1) / 2;
+
+        let item = if group.before == 0 {
+            iter.next()
+        } else if group.before <= walk_back_steps {
+            let item = iter.nth(group.before);
+            group.before = 0;
+            item
+        } else {
+            (group.nth_back)(iter, group.len - 1)
+        };
+
+        group.len -= 1;
+        item
+    }
 }
 
 impl<I, F> Iterator for SplitIterator<I, F>
@@ -39,9 +107,13 @@ where
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(group) = &mut self.back {
+            return Self::next_counted(self.iter.as_mut()?, group);
+        }
+
         let item = self.iter.as_mut()?.next()?;
 
-        if (self.pred)(&item) {
+        if self.pred.as_mut().is_some_and(|pred| pred(&item)) {
             self.iter = None;
             return None;
         }
@@ -50,6 +122,29 @@ where
     }
 }
 
+/// Advances `iter` past the next group and the separator that ends it. Returns the number of items
+/// in the group. `iter` is set to `None` if the group is the last one.
+fn skip_group<I, F>(iter: &mut Option<I>, pred: &mut F) -> usize
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    let mut len = 0;
+
+    loop {
+        let Some(item) = iter.as_mut().and_then(Iterator::next) else {
+            *iter = None;
+            return len;
+        };
+
+        if pred(&item) {
+            return len;
+        }
+
+        len += 1;
+    }
+}
+
 impl<I, F> Iterator for Split<I, F>
 where
     I: Iterator + Clone,
@@ -59,25 +154,235 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let next_iter = self.iter.clone()?;
+        let group_len = skip_group(&mut self.iter, &mut self.pred);
 
-        loop {
-            let Some(item) = self.iter.as_mut()?.next() else {
-                self.iter = None;
-                break;
-            };
+        if let Some(len) = &mut self.len {
+            // The separator after the group was skipped too, unless this was the last group
+            *len = len.saturating_sub(group_len + usize::from(self.iter.is_some()));
+        }
 
-            if (self.pred)(&item) {
-                break;
+        Some(SplitIterator::new(next_iter, &self.pred))
+    }
+}
+
+impl<I, F> DoubleEndedIterator for Split<I, F>
+where
+    I: DoubleEndedIterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let iter = self.iter.as_mut()?;
+        let len = *self.len.get_or_insert_with(|| iter.clone().count());
+        let group = iter.clone();
+        let mut group_len = 0;
+
+        // Take the group and the separator before it off the back
+        loop {
+            match iter.next_back() {
+                Some(item) if (self.pred)(&item) => break,
+                Some(_) => group_len += 1,
+                None => {
+                    self.iter = None;
+                    break;
+                }
             }
         }
 
+        // The items before the group include the separator
+        let before = len - group_len;
+        self.len = Some(before.saturating_sub(1));
+
         Some(SplitIterator {
-            iter: Some(next_iter),
-            pred: self.pred.clone(),
+            iter: Some(group),
+            pred: None,
+            back: Some(BackGroup {
+                before,
+                len: group_len,
+                nth_back: |iter, n| iter.clone().nth_back(n),
+            }),
         })
     }
 }
 
+/// Iterator returned by
+/// [`Iterator::split_terminator`](crate::prelude::IterCloneExt::split_terminator)
+pub struct SplitTerminator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    iter: Option<I>,
+    pred: F,
+}
+
+impl<I, F> SplitTerminator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            iter: Some(iter),
+            pred,
+        }
+    }
+}
+
+impl<I, F> Iterator for SplitTerminator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = SplitIterator<I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_iter = self.iter.clone()?;
+        let len = skip_group(&mut self.iter, &mut self.pred);
+
+        if len == 0 && self.iter.is_none() {
+            return None;
+        }
+
+        Some(SplitIterator::new(next_iter, &self.pred))
+    }
+}
+
+/// Iterator returned by [`Iterator::splitn`](crate::prelude::IterCloneExt::splitn)
+pub struct SplitN<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    iter: Option<I>,
+    pred: F,
+    remaining: usize,
+}
+
+impl<I, F> SplitN<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    pub(super) fn new(iter: I, n: usize, pred: F) -> Self {
+        Self {
+            iter: Some(iter),
+            pred,
+            remaining: n,
+        }
+    }
+}
+
+impl<I, F> Iterator for SplitN<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = SplitIterator<I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+
+        if self.remaining == 0 {
+            return Some(SplitIterator {
+                iter: Some(self.iter.take()?),
+                pred: None,
+                back: None,
+            });
+        }
+
+        let next_iter = self.iter.clone()?;
+        skip_group(&mut self.iter, &mut self.pred);
+
+        Some(SplitIterator::new(next_iter, &self.pred))
+    }
+}
+
+/// Iterator returned by [`Iterator::split_nonempty`](crate::prelude::IterCloneExt::split_nonempty)
+pub struct SplitNonEmpty<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    iter: Option<I>,
+    pred: F,
+}
+
+impl<I, F> SplitNonEmpty<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            iter: Some(iter),
+            pred,
+        }
+    }
+}
+
+impl<I, F> Iterator for SplitNonEmpty<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = SplitIterator<I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next_iter = self.iter.clone()?;
+
+            if skip_group(&mut self.iter, &mut self.pred) > 0 {
+                return Some(SplitIterator::new(next_iter, &self.pred));
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`Iterator::rsplit`](crate::prelude::IterCloneExt::rsplit)
+///
+/// This takes groups from the back of a [`Split`], so its groups are found the same way.
+pub struct RSplit<I, F>
+where
+    I: DoubleEndedIterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    inner: Split<I, F>,
+}
+
+impl<I, F> RSplit<I, F>
+where
+    I: DoubleEndedIterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            inner: Split::new(iter, pred),
+        }
+    }
+}
+
+impl<I, F> Iterator for RSplit<I, F>
+where
+    I: DoubleEndedIterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = SplitIterator<I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<I, F> DoubleEndedIterator for RSplit<I, F>
+where
+    I: DoubleEndedIterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
 /// Iterator returned by [`Iterator::split_inclusive`](crate::prelude::IterCloneExt::split_inclusive)
 pub struct SplitInclusive<I, F>
 where
@@ -138,25 +443,390 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let next_iter = self.iter.clone()?;
+        skip_group(&mut self.iter, &mut self.pred);
+
+        Some(InclusiveSplitIterator {
+            iter: Some(next_iter),
+            pred: self.pred.clone(),
+        })
+    }
+}
+
+/// Iterator returned by
+/// [`Iterator::split_initiator`](crate::prelude::IterCloneExt::split_initiator)
+pub struct SplitInitiator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    iter: Option<I>,
+    pred: F,
+    /// Whether the next group starts with a separator
+    started: bool,
+}
+
+impl<I, F> SplitInitiator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            iter: Some(iter),
+            pred,
+            started: false,
+        }
+    }
+}
+
+/// Iterator used by [`Iterator::split_initiator`](crate::prelude::IterCloneExt::split_initiator)
+pub struct InitiatorSplitIterator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    iter: Option<I>,
+    pred: F,
+    /// Whether the next item is the separator that starts the group
+    at_separator: bool,
+}
+
+impl<I, F> Iterator for InitiatorSplitIterator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.as_mut()?.next()?;
+
+        if !core::mem::take(&mut self.at_separator) && (self.pred)(&item) {
+            self.iter = None;
+            return None;
+        }
+
+        Some(item)
+    }
+}
 
+impl<I, F> Iterator for SplitInitiator<I, F>
+where
+    I: Iterator + Clone,
+    F: FnMut(&I::Item) -> bool + Clone,
+{
+    type Item = InitiatorSplitIterator<I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let iter = self.iter.as_mut()?;
+        let next_iter = iter.clone();
+        let at_separator = core::mem::replace(&mut self.started, true);
+
+        if at_separator {
+            iter.next();
+        }
+
+        // Stop before the next separator so that it starts the next group
         loop {
-            let Some(item) = self.iter.as_mut()?.next() else {
+            let before = iter.clone();
+
+            let Some(item) = iter.next() else {
                 self.iter = None;
                 break;
             };
 
             if (self.pred)(&item) {
+                *iter = before;
                 break;
             }
         }
 
-        Some(InclusiveSplitIterator {
+        Some(InitiatorSplitIterator {
             iter: Some(next_iter),
             pred: self.pred.clone(),
+            at_separator,
         })
     }
 }
 
+/// Iterator returned by [`Iterator::split_buffered`](crate::prelude::IterExt::split_buffered)
+#[cfg(feature = "alloc")]
+pub struct SplitBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    iter: I,
+    pred: F,
+    finished: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> SplitBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            iter,
+            pred,
+            finished: false,
+        }
+    }
+
+    /// Collects the next group, calling `on_separator` with the group and the separator that ends
+    /// it.
+    fn next_group(
+        &mut self,
+        on_separator: impl FnOnce(&mut Vec<I::Item>, I::Item),
+    ) -> Option<Vec<I::Item>> {
+        if self.finished {
+            return None;
+        }
+
+        let mut group = Vec::new();
+
+        for item in self.iter.by_ref() {
+            if (self.pred)(&item) {
+                on_separator(&mut group, item);
+                return Some(group);
+            }
+
+            group.push(item);
+        }
+
+        self.finished = true;
+        Some(group)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> Iterator for SplitBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_group(|_, _| {})
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> FusedIterator for SplitBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+}
+
+/// Iterator returned by
+/// [`Iterator::split_inclusive_buffered`](crate::prelude::IterExt::split_inclusive_buffered)
+#[cfg(feature = "alloc")]
+pub struct SplitInclusiveBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    inner: SplitBuffered<I, F>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> SplitInclusiveBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    pub(super) fn new(iter: I, pred: F) -> Self {
+        Self {
+            inner: SplitBuffered::new(iter, pred),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> Iterator for SplitInclusiveBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_group(|group, separator| group.push(separator))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> FusedIterator for SplitInclusiveBuffered<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+}
+
+/// Iterator returned by [`Iterator::split_on_seq`](crate::prelude::IterExt::split_on_seq)
+#[cfg(feature = "alloc")]
+pub struct SplitOnSeq<'d, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    iter: I,
+    delimiter: &'d [I::Item],
+    /// The length of the longest proper prefix of `delimiter[..=i]` that is also its suffix
+    failure: Vec<usize>,
+    /// Whether the empty group before the first item of an empty delimiter was yielded
+    started: bool,
+    finished: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<'d, I> SplitOnSeq<'d, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    pub(super) fn new(iter: I, delimiter: &'d [I::Item]) -> Self {
+        let mut failure = alloc::vec![0; delimiter.len()];
+        let mut matched = 0;
+
+        for i in 1..delimiter.len() {
+            while matched > 0 && delimiter[i] != delimiter[matched] {
+                matched = failure[matched - 1];
+            }
+
+            if delimiter[i] == delimiter[matched] {
+                matched += 1;
+            }
+
+            failure[i] = matched;
+        }
+
+        Self {
+            iter,
+            delimiter,
+            failure,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Collects the next group. The delimiter that ends the group is kept if `inclusive` is set.
+    fn next_group(&mut self, inclusive: bool) -> Option<Vec<I::Item>> {
+        if self.finished {
+            return None;
+        }
+
+        // An empty delimiter matches between every item
+        if self.delimiter.is_empty() {
+            if !core::mem::replace(&mut self.started, true) {
+                return Some(Vec::new());
+            }
+
+            let item = self.iter.next();
+            self.finished = item.is_none();
+            return Some(item.into_iter().collect());
+        }
+
+        let mut group = Vec::new();
+        // The length of the delimiter prefix that the group ends with
+        let mut matched = 0;
+
+        for item in self.iter.by_ref() {
+            while matched > 0 && item != self.delimiter[matched] {
+                matched = self.failure[matched - 1];
+            }
+
+            if item == self.delimiter[matched] {
+                matched += 1;
+            }
+
+            group.push(item);
+
+            if matched == self.delimiter.len() {
+                if !inclusive {
+                    group.truncate(group.len() - matched);
+                }
+
+                return Some(group);
+            }
+        }
+
+        self.finished = true;
+        Some(group)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> Iterator for SplitOnSeq<'_, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_group(false)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FusedIterator for SplitOnSeq<'_, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+}
+
+/// Iterator returned by
+/// [`Iterator::split_on_seq_inclusive`](crate::prelude::IterExt::split_on_seq_inclusive)
+#[cfg(feature = "alloc")]
+pub struct SplitOnSeqInclusive<'d, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    inner: SplitOnSeq<'d, I>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'d, I> SplitOnSeqInclusive<'d, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    pub(super) fn new(iter: I, delimiter: &'d [I::Item]) -> Self {
+        Self {
+            inner: SplitOnSeq::new(iter, delimiter),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> Iterator for SplitOnSeqInclusive<'_, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_group(true)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FusedIterator for SplitOnSeqInclusive<'_, I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -209,4 +879,128 @@ mod tests {
             assert_eq!(&split_nums, expected_output);
         }
     }
+
+    fn collect_groups<G: Iterator<Item = u32>>(groups: impl Iterator<Item = G>) -> Vec<Vec<u32>> {
+        groups.map(|group| group.collect()).collect()
+    }
+
+    #[test]
+    fn split_modes() {
+        let input = [0u32, 10, 20, 0, 0, 50, 0];
+        let split = || input.iter().copied();
+        let is_zero = |n: &u32| *n == 0;
+
+        let terminated: &[&[u32]] = &[&[], &[10, 20], &[], &[50]];
+        assert_eq!(
+            collect_groups(split().split_terminator(is_zero)),
+            terminated
+        );
+        assert!(collect_groups([].into_iter().split_terminator(is_zero)).is_empty());
+
+        let first_two: &[&[u32]] = &[&[], &[10, 20, 0, 0, 50, 0]];
+        assert_eq!(collect_groups(split().splitn(2, is_zero)), first_two);
+        assert!(collect_groups(split().splitn(0, is_zero)).is_empty());
+        assert_eq!(collect_groups(split().splitn(9, is_zero)).len(), 5);
+
+        let initiated: &[&[u32]] = &[&[], &[0, 10, 20], &[0], &[0, 50], &[0]];
+        assert_eq!(collect_groups(split().split_initiator(is_zero)), initiated);
+        let empty: &[&[u32]] = &[&[]];
+        assert_eq!(
+            collect_groups([].into_iter().split_initiator(is_zero)),
+            empty
+        );
+
+        let nonempty: &[&[u32]] = &[&[10, 20], &[50]];
+        assert_eq!(collect_groups(split().split_nonempty(is_zero)), nonempty);
+
+        let reversed: &[&[u32]] = &[&[], &[50], &[], &[10, 20], &[]];
+        assert_eq!(collect_groups(split().rsplit(is_zero)), reversed);
+    }
+
+    #[test]
+    fn split_from_both_ends() {
+        let is_zero = |n: &u32| *n == 0;
+
+        for input in [&[0u32, 10, 20, 0, 0, 50, 0][..], &[1, 0, 2], &[0], &[]] {
+            let expected = collect_groups(input.iter().copied().split(is_zero));
+
+            for split in 0..=expected.len() {
+                let mut groups = input.iter().copied().split(is_zero);
+                let mut front = collect_groups(groups.by_ref().take(split));
+                let back = collect_groups(groups.rev());
+
+                front.extend(back.into_iter().rev());
+                assert_eq!(front, expected, "{input:?} {split}");
+            }
+        }
+    }
+
+    /// An iterator that can only be walked once.
+    #[cfg(feature = "alloc")]
+    struct Stream<I>(I);
+
+    #[cfg(feature = "alloc")]
+    impl<I: Iterator> Iterator for Stream<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_buffered() {
+        let input = [0u32, 10, 20, 0, 0, 50, 0];
+        let mut calls = 0;
+
+        for (split_nums, expected) in [
+            (&input[..], &[&[][..], &[10, 20], &[], &[50], &[]][..]),
+            (&[], &[&[]]),
+        ] {
+            let split_nums: Vec<Vec<u32>> = Stream(split_nums.iter().copied())
+                .split_buffered(|n| {
+                    calls += 1;
+                    *n == 0
+                })
+                .collect();
+
+            assert_eq!(&split_nums, expected);
+        }
+
+        assert_eq!(calls, input.len());
+
+        let inclusive: Vec<Vec<u32>> = Stream(input.iter().copied().chain([2]))
+            .split_inclusive_buffered(|n| *n == 0)
+            .collect();
+
+        assert_eq!(inclusive, [&[0][..], &[10, 20, 0], &[0], &[50, 0], &[2]]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_on_seq_matches_slices() {
+        let cases: [(&[u8], &[u8]); 6] = [
+            (b"a\r\nb\r\n", b"\r\n"),
+            (b"aaabaab", b"aab"),
+            (b"abababc", b"ababc"),
+            (b"xyz", b"xyzw"),
+            (b"", b"ab"),
+            (b"ab", b""),
+        ];
+
+        for (input, delimiter) in cases {
+            let split: Vec<Vec<u8>> = Stream(input.iter().copied())
+                .split_on_seq(delimiter)
+                .collect();
+            let expected: Vec<&[u8]> = input.split_seq(delimiter).collect();
+            assert_eq!(split, expected, "{input:?} {delimiter:?}");
+
+            let inclusive: Vec<Vec<u8>> = Stream(input.iter().copied())
+                .split_on_seq_inclusive(delimiter)
+                .collect();
+            assert_eq!(inclusive.concat(), input);
+            assert_eq!(inclusive.len(), expected.len());
+        }
+    }
 }
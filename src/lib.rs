@@ -9,18 +9,33 @@ pub mod prelude {
     pub use crate::iter::IterCloneExt;
     pub use crate::iter::IterExt;
     pub use crate::slice::SliceExt;
+    #[cfg(feature = "alloc")]
+    pub use crate::slice::VecExt;
+    pub use crate::span::SpanArithmetic;
     pub use crate::str::StrExt;
 }
 
+pub mod array_string;
+pub mod array_vec;
+#[cfg(feature = "alloc")]
+pub mod diagnostic;
 pub mod iter;
 pub mod slice;
+#[cfg(feature = "alloc")]
+pub mod source_map;
 pub mod span;
 pub mod str;
 
 mod cstr;
+#[cfg(all(test, feature = "serde", feature = "alloc"))]
+mod test_formats;
+#[cfg(test)]
+mod test_utils;
 mod transmute;
 mod wrappers;
 
+pub use crate::array_string::ArrayString;
+pub use crate::array_vec::ArrayVec;
 pub use crate::span::s::Span;
 pub use crate::transmute::*;
 pub use crate::wrappers::*;
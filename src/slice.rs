@@ -1,7 +1,25 @@
 //! Contains extentions for rust slices.
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use crate::span::Span;
+
+mod find;
+mod matches;
+#[cfg(feature = "alloc")]
+mod multi_find;
+mod split_seq;
 mod with_sequence_removed;
-pub use with_sequence_removed::WithSequenceRemoved;
+pub use find::{Finder, memchr, memrchr};
+pub use matches::{FindIter, FindOverlappingIter, MatchSpans, RFindIter, RMatchSpans};
+#[cfg(feature = "alloc")]
+pub use multi_find::{MatchKind, MultiFindIter, MultiFindOverlappingIter, MultiFinder};
+pub use split_seq::{RSplitSeq, SplitNSeq, SplitSeq, SplitSeqInclusive};
+#[cfg(feature = "alloc")]
+pub use with_sequence_removed::WithSequencesRemoved;
+pub use with_sequence_removed::{WithSequenceRemoved, WithSequenceReplaced};
 
 impl<T> SliceExt for [T] {
     type Inner = T;
@@ -10,7 +28,95 @@ impl<T> SliceExt for [T] {
     where
         T: PartialEq,
     {
-        self.windows(subslice.len()).position(|w| w == subslice)
+        find::find(self, subslice)
+    }
+
+    fn rfind(&self, subslice: &[T]) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        find::rfind(self, subslice)
+    }
+
+    fn find_iter<'a>(&'a self, subslice: &'a [T]) -> FindIter<'a, T>
+    where
+        T: PartialEq,
+    {
+        FindIter::new(self, subslice)
+    }
+
+    fn rfind_iter<'a>(&'a self, subslice: &'a [T]) -> RFindIter<'a, T>
+    where
+        T: PartialEq,
+    {
+        RFindIter::new(self, subslice)
+    }
+
+    fn find_overlapping_iter<'a>(&'a self, subslice: &'a [T]) -> FindOverlappingIter<'a, T>
+    where
+        T: PartialEq,
+    {
+        FindOverlappingIter::new(self, subslice)
+    }
+
+    fn match_spans<'a>(&'a self, subslice: &'a [T]) -> MatchSpans<'a, T>
+    where
+        T: PartialEq,
+    {
+        MatchSpans::new(self, subslice)
+    }
+
+    fn rmatch_spans<'a>(&'a self, subslice: &'a [T]) -> RMatchSpans<'a, T>
+    where
+        T: PartialEq,
+    {
+        RMatchSpans::new(self, subslice)
+    }
+
+    fn split_seq<'a>(&'a self, delimiter: &'a [T]) -> SplitSeq<'a, T>
+    where
+        T: PartialEq,
+    {
+        SplitSeq::new(self, delimiter)
+    }
+
+    fn split_seq_inclusive<'a>(&'a self, delimiter: &'a [T]) -> SplitSeqInclusive<'a, T>
+    where
+        T: PartialEq,
+    {
+        SplitSeqInclusive::new(self, delimiter)
+    }
+
+    fn splitn_seq<'a>(&'a self, n: usize, delimiter: &'a [T]) -> SplitNSeq<'a, T>
+    where
+        T: PartialEq,
+    {
+        SplitNSeq::new(self, delimiter, n)
+    }
+
+    fn rsplit_seq<'a>(&'a self, delimiter: &'a [T]) -> RSplitSeq<'a, T>
+    where
+        T: PartialEq,
+    {
+        RSplitSeq::new(self, delimiter)
+    }
+
+    fn split_once_seq(&self, delimiter: &[T]) -> Option<(&[T], &[T])>
+    where
+        T: PartialEq,
+    {
+        let index = self.find(delimiter)?;
+
+        Some((&self[..index], &self[index + delimiter.len()..]))
+    }
+
+    fn rsplit_once_seq(&self, delimiter: &[T]) -> Option<(&[T], &[T])>
+    where
+        T: PartialEq,
+    {
+        let index = self.rfind(delimiter)?;
+
+        Some((&self[..index], &self[index + delimiter.len()..]))
     }
 
     fn with_sequence_removed<'a>(&'a self, sequence: &'a [T]) -> WithSequenceRemoved<'a, T>
@@ -20,6 +126,77 @@ impl<T> SliceExt for [T] {
         WithSequenceRemoved::new(self, sequence)
     }
 
+    fn with_sequence_replaced<'a>(
+        &'a self,
+        sequence: &'a [T],
+        replacement: &'a [T],
+    ) -> WithSequenceReplaced<'a, T>
+    where
+        T: PartialEq,
+    {
+        WithSequenceReplaced::new(self, sequence, replacement, usize::MAX)
+    }
+
+    fn with_sequence_replacedn<'a>(
+        &'a self,
+        sequence: &'a [T],
+        replacement: &'a [T],
+        n: usize,
+    ) -> WithSequenceReplaced<'a, T>
+    where
+        T: PartialEq,
+    {
+        WithSequenceReplaced::new(self, sequence, replacement, n)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn replace_seq(&self, sequence: &[T], replacement: &[T]) -> Vec<T>
+    where
+        T: Clone + PartialEq,
+    {
+        self.replacen(sequence, replacement, usize::MAX)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn replacen(&self, sequence: &[T], replacement: &[T], n: usize) -> Vec<T>
+    where
+        T: Clone + PartialEq,
+    {
+        self.with_sequence_replacedn(sequence, replacement, n)
+            .cloned()
+            .collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn with_sequences_removed<'a>(&'a self, sequences: &[&'a [T]]) -> WithSequencesRemoved<'a, T>
+    where
+        T: PartialEq,
+    {
+        WithSequencesRemoved::new(self, sequences)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn find_any(&self, patterns: &[&[T]]) -> Option<(usize, Span)>
+    where
+        T: PartialEq,
+    {
+        MultiFinder::new(patterns).find(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn replace_all_many(&self, replacements: &[(&[T], &[T])]) -> Vec<T>
+    where
+        T: Clone + PartialEq,
+    {
+        let patterns: Vec<&[T]> = replacements.iter().map(|(pattern, _)| *pattern).collect();
+        let replacements: Vec<&[T]> = replacements
+            .iter()
+            .map(|(_, replacement)| *replacement)
+            .collect();
+
+        MultiFinder::new(&patterns).replace_all(self, &replacements)
+    }
+
     fn get_slice_between(&self, slice1: &[T], slice2: &[T]) -> Option<&[T]>
     where
         T: PartialEq,
@@ -31,16 +208,206 @@ impl<T> SliceExt for [T] {
 
         slice.get(0..end_idx)
     }
+
+    fn subslice_pos(&self, subslice: &[T]) -> Option<Span> {
+        let range = self.as_ptr_range();
+        let sub_range = subslice.as_ptr_range();
+
+        if size_of::<T>() == 0 {
+            // Every element of a zero-sized slice has the same address
+            return (sub_range.start == range.start && subslice.len() <= self.len())
+                .then(|| Span::from(0..subslice.len()));
+        }
+
+        if sub_range.start < range.start || sub_range.end > range.end {
+            return None;
+        }
+
+        let offset = sub_range.start.addr() - range.start.addr();
+        if !offset.is_multiple_of(size_of::<T>()) {
+            return None;
+        }
+
+        Some(Span::at(offset / size_of::<T>()).with_len(subslice.len()))
+    }
 }
 
 pub trait SliceExt {
     type Inner;
 
-    /// Returns the starting index of the first occurence of `subslice`.
+    /// Returns the starting index of the first occurence of `subslice`. An empty `subslice` is
+    /// found at index `0`.
+    ///
+    /// This uses the Two-Way algorithm, so it runs in linear time in the length of the slice. It
+    /// only compares elements for equality, so preprocessing the needle can take `O(m * m)` time;
+    /// [`Finder`] preprocesses it in linear time for elements that implement [`Ord`]. To search
+    /// for a single byte a word at a time, use [`memchr`] or [`Finder::new_bytes`].
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!([1.0, 2.5, 2.5, 3.0].find(&[2.5, 3.0]), Some(2));
+    /// assert_eq!(b"abc".find(b""), Some(0));
+    /// assert_eq!(b"abc".find(b"abcd"), None);
+    /// ```
     fn find(&self, subslice: &Self) -> Option<usize>
     where
         Self::Inner: PartialEq;
 
+    /// Returns the starting index of the last occurence of `subslice`. An empty `subslice` is found
+    /// at the end of the slice.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!(b"abcabc".rfind(b"bc"), Some(4));
+    /// assert_eq!(b"abc".rfind(b""), Some(3));
+    /// assert_eq!(b"abc".rfind(b"d"), None);
+    /// ```
+    fn rfind(&self, subslice: &Self) -> Option<usize>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns an iterator over the starting indices of the non-overlapping occurences of
+    /// `subslice`, searching from the front.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let matches: Vec<usize> = b"aaaaa".find_iter(b"aa").collect();
+    /// assert_eq!(matches, [0, 2]);
+    /// ```
+    fn find_iter<'a>(&'a self, subslice: &'a Self) -> FindIter<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns an iterator over the starting indices of the non-overlapping occurences of
+    /// `subslice`, searching from the back.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let matches: Vec<usize> = b"aaaaa".rfind_iter(b"aa").collect();
+    /// assert_eq!(matches, [3, 1]);
+    /// ```
+    fn rfind_iter<'a>(&'a self, subslice: &'a Self) -> RFindIter<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns a double-ended iterator over the starting indices of every occurence of
+    /// `subslice`, including ones that overlap.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let mut matches = b"aaaaa".find_overlapping_iter(b"aa");
+    ///
+    /// assert_eq!(matches.next(), Some(0));
+    /// assert_eq!(matches.next_back(), Some(3));
+    /// assert_eq!(matches.collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    fn find_overlapping_iter<'a>(
+        &'a self,
+        subslice: &'a Self,
+    ) -> FindOverlappingIter<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns an iterator over the spans of the non-overlapping occurences of `subslice`.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let spans: Vec<Span> = b"one, two, three".match_spans(b", ").collect();
+    /// assert_eq!(spans, [Span::from(3..5), Span::from(8..10)]);
+    /// ```
+    fn match_spans<'a>(&'a self, subslice: &'a Self) -> MatchSpans<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns an iterator over the spans of the non-overlapping occurences of `subslice`,
+    /// searching from the back. Like [`rfind_iter`](SliceExt::rfind_iter), this can find
+    /// different matches than [`match_spans`](SliceExt::match_spans) when `subslice` overlaps
+    /// itself.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let spans: Vec<Span> = b"aaaaa".rmatch_spans(b"aa").collect();
+    /// assert_eq!(spans, [Span::from(3..5), Span::from(1..3)]);
+    /// ```
+    fn rmatch_spans<'a>(&'a self, subslice: &'a Self) -> RMatchSpans<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Returns an iterator over the subslices separated by `delimiter`. Unlike [`slice::split`],
+    /// the delimiter is a sequence of elements rather than a single element.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let message = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\nbody";
+    ///
+    /// let parts: Vec<&[u8]> = message.split_seq(b"\r\n\r\n").collect();
+    /// assert_eq!(parts, [&b"GET / HTTP/1.1\r\nHost: example.com"[..], b"body"]);
+    ///
+    /// let lines: Vec<&[u8]> = b"a\r\nb\r\n".split_seq(b"\r\n").collect();
+    /// assert_eq!(lines, [&b"a"[..], b"b", b""]);
+    /// ```
+    fn split_seq<'a>(&'a self, delimiter: &'a Self) -> SplitSeq<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Like [`split_seq`](SliceExt::split_seq), but each subslice includes the delimiter that ends
+    /// it. A trailing delimiter does not produce an empty subslice.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let lines: Vec<&[u8]> = b"a\r\nb\r\n".split_seq_inclusive(b"\r\n").collect();
+    /// assert_eq!(lines, [&b"a\r\n"[..], b"b\r\n"]);
+    /// ```
+    fn split_seq_inclusive<'a>(&'a self, delimiter: &'a Self) -> SplitSeqInclusive<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Like [`split_seq`](SliceExt::split_seq), but returns at most `n` subslices. The last
+    /// subslice contains the rest of the slice.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let fields: Vec<&[u8]> = b"a::b::c".splitn_seq(2, b"::").collect();
+    /// assert_eq!(fields, [&b"a"[..], b"b::c"]);
+    /// ```
+    fn splitn_seq<'a>(&'a self, n: usize, delimiter: &'a Self) -> SplitNSeq<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Like [`split_seq`](SliceExt::split_seq), but starts from the back of the slice.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let fields: Vec<&[u8]> = b"a::b::c".rsplit_seq(b"::").collect();
+    /// assert_eq!(fields, [&b"c"[..], b"b", b"a"]);
+    /// ```
+    fn rsplit_seq<'a>(&'a self, delimiter: &'a Self) -> RSplitSeq<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Splits the slice around the first occurence of `delimiter`.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!(b"key: value: x".split_once_seq(b": "), Some((&b"key"[..], &b"value: x"[..])));
+    /// assert_eq!(b"key".split_once_seq(b": "), None);
+    /// ```
+    fn split_once_seq(&self, delimiter: &Self) -> Option<(&Self, &Self)>
+    where
+        Self::Inner: PartialEq;
+
+    /// Splits the slice around the last occurence of `delimiter`.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!(b"key: value: x".rsplit_once_seq(b": "), Some((&b"key: value"[..], &b"x"[..])));
+    /// ```
+    fn rsplit_once_seq(&self, delimiter: &Self) -> Option<(&Self, &Self)>
+    where
+        Self::Inner: PartialEq;
+
     /// Returns an iterator with the provided sequence filtered out once.
     /// # Example
     /// ```
@@ -61,6 +428,122 @@ pub trait SliceExt {
     where
         Self::Inner: PartialEq;
 
+    /// Returns an iterator with the provided sequence replaced once. Like
+    /// [`with_sequence_removed`](SliceExt::with_sequence_removed), occurences are replaced from
+    /// left to right without overlapping. An empty sequence is replaced before every element and
+    /// at the end.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let replaced: Vec<u8> = b"aaaaa".with_sequence_replaced(b"aa", b"b").copied().collect();
+    /// assert_eq!(&replaced, b"bba");
+    ///
+    /// let replaced: Vec<u8> = b"ab".with_sequence_replaced(b"", b"-").copied().collect();
+    /// assert_eq!(&replaced, b"-a-b-");
+    /// ```
+    fn with_sequence_replaced<'a>(
+        &'a self,
+        sequence: &'a [Self::Inner],
+        replacement: &'a [Self::Inner],
+    ) -> WithSequenceReplaced<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Like [`with_sequence_replaced`](SliceExt::with_sequence_replaced), but only replaces the
+    /// first `n` occurences.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let replaced: Vec<u8> = b"a, b, c".with_sequence_replacedn(b", ", b";", 1).copied().collect();
+    /// assert_eq!(&replaced, b"a;b, c");
+    ///
+    /// let replaced: Vec<u8> = b"ab".with_sequence_replacedn(b"", b"-", 2).copied().collect();
+    /// assert_eq!(&replaced, b"-a-b");
+    /// ```
+    fn with_sequence_replacedn<'a>(
+        &'a self,
+        sequence: &'a [Self::Inner],
+        replacement: &'a [Self::Inner],
+        n: usize,
+    ) -> WithSequenceReplaced<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Replaces every occurence of `sequence` with `replacement`. This collects
+    /// [`with_sequence_replaced`](SliceExt::with_sequence_replaced) into a [`Vec`].
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!(b"a, b, c".replace_seq(b", ", b";"), b"a;b;c");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn replace_seq(&self, sequence: &Self, replacement: &Self) -> Vec<Self::Inner>
+    where
+        Self::Inner: Clone + PartialEq;
+
+    /// Replaces the first `n` occurences of `sequence` with `replacement`. This collects
+    /// [`with_sequence_replacedn`](SliceExt::with_sequence_replacedn) into a [`Vec`].
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// assert_eq!(b"a, b, c".replacen(b", ", b";", 1), b"a;b, c");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn replacen(&self, sequence: &Self, replacement: &Self, n: usize) -> Vec<Self::Inner>
+    where
+        Self::Inner: Clone + PartialEq;
+
+    /// Returns an iterator with every occurence of any of the provided sequences filtered out
+    /// once. Where sequences overlap, the one that starts first is removed, preferring sequences
+    /// that are given first.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let text = b"user=alice password=hunter2 token=abc";
+    ///
+    /// let scrubbed: Vec<u8> = text
+    ///     .with_sequences_removed(&[b"hunter2", b"abc"])
+    ///     .copied()
+    ///     .collect();
+    /// assert_eq!(&scrubbed, b"user=alice password= token=");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn with_sequences_removed<'a>(
+        &'a self,
+        sequences: &[&'a [Self::Inner]],
+    ) -> WithSequencesRemoved<'a, Self::Inner>
+    where
+        Self::Inner: PartialEq;
+
+    /// Finds the first occurence of any of `patterns`. Returns the index of the pattern and the
+    /// span of the match. If several patterns match at the same index, the one given first is
+    /// returned. Use [`MultiFinder`] to search for the same patterns many times.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let source = b"let x = 5; // five";
+    ///
+    /// assert_eq!(source.find_any(&[b"//", b";", b"="]), Some((2, Span::from(6..7))));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn find_any(&self, patterns: &[&Self]) -> Option<(usize, Span)>
+    where
+        Self::Inner: PartialEq;
+
+    /// Replaces every occurence of each pattern with its replacement in one pass. Where patterns
+    /// overlap, the one that starts first is replaced, preferring patterns that are given first.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let escaped = b"<a & b>".replace_all_many(&[(b"<", b"&lt;"), (b">", b"&gt;"), (b"&", b"&amp;")]);
+    /// assert_eq!(&escaped, b"&lt;a &amp; b&gt;");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn replace_all_many(&self, replacements: &[(&Self, &Self)]) -> Vec<Self::Inner>
+    where
+        Self::Inner: Clone + PartialEq;
+
     /// Gets the content between the first occurance of two subslices.
     /// # Example
     /// ```
@@ -74,4 +557,140 @@ pub trait SliceExt {
     fn get_slice_between(&self, slice1: &Self, slice2: &Self) -> Option<&Self>
     where
         Self::Inner: PartialEq;
+
+    /// Gets the position of a subslice that points into this slice. Returns `None` if `subslice`
+    /// is not contained in `self`.
+    ///
+    /// The elements of a slice of zero-sized types all share one address, so such subslices are
+    /// always reported to start at `0`.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let numbers = [1u32, 2, 3, 4, 5];
+    ///
+    /// assert_eq!(numbers.subslice_pos(&numbers[1..4]), Some(Span::from(1..4)));
+    /// assert_eq!(numbers.subslice_pos(&numbers[5..]), Some(Span::from(5..5)));
+    /// assert_eq!(numbers.subslice_pos(&[2, 3]), None);
+    /// ```
+    fn subslice_pos(&self, subslice: &Self) -> Option<Span>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> VecExt for Vec<T> {
+    type Inner = T;
+
+    fn replace_seq_in_place(&mut self, sequence: &[T], replacement: &[T]) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        assert!(
+            replacement.len() <= sequence.len(),
+            "replacement is longer than the sequence it replaces"
+        );
+
+        if sequence.is_empty() {
+            return self.len() + 1;
+        }
+
+        let searcher = find::Searcher::new(sequence);
+        let mut read = 0;
+        let mut write = 0;
+        let mut count = 0;
+
+        while let Some(offset) = searcher.find(&self[read..]) {
+            for i in read..read + offset {
+                self.swap(write, i);
+                write += 1;
+            }
+
+            self[write..write + replacement.len()].clone_from_slice(replacement);
+            write += replacement.len();
+            read += offset + sequence.len();
+            count += 1;
+        }
+
+        for i in read..self.len() {
+            self.swap(write, i);
+            write += 1;
+        }
+
+        self.truncate(write);
+        count
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait VecExt {
+    type Inner;
+
+    /// Replaces every occurence of `sequence` with `replacement` without allocating, and returns
+    /// the number of replacements. Occurences are replaced like in
+    /// [`SliceExt::replace_seq`].
+    /// # Panics
+    /// Panics if `replacement` is longer than `sequence`.
+    /// # Example
+    /// ```
+    /// # use wutil::prelude::*;
+    /// let mut text = b"a\r\nb\r\n".to_vec();
+    ///
+    /// assert_eq!(text.replace_seq_in_place(b"\r\n", b"\n"), 2);
+    /// assert_eq!(text, b"a\nb\n");
+    /// ```
+    fn replace_seq_in_place(
+        &mut self,
+        sequence: &[Self::Inner],
+        replacement: &[Self::Inner],
+    ) -> usize
+    where
+        Self::Inner: Clone + PartialEq;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subslice_pos_edges() {
+        let pairs = [[1u8, 2], [3, 4], [5, 6]];
+        let bytes = pairs.as_flattened();
+
+        // Starts between two elements
+        let shifted: &[[u8; 2]] =
+            unsafe { core::slice::from_raw_parts(bytes[1..].as_ptr().cast(), 2) };
+        assert_eq!(pairs.subslice_pos(shifted), None);
+
+        let words = [1u16, 2, 3, 4];
+        assert_eq!(words[..2].subslice_pos(&words[1..3]), None);
+        assert_eq!(words[1..].subslice_pos(&words[..1]), None);
+
+        let units = [(); 4];
+        assert_eq!(units.subslice_pos(&units[1..3]), Some(Span::from(0..2)));
+        assert_eq!(units.subslice_pos(&[(); 5]), None);
+    }
+
+    #[test]
+    fn empty_sequence_removed() {
+        assert!(b"ab".with_sequence_removed(b"").eq(b"ab"));
+        assert!(b"".with_sequence_removed(b"").eq(b""));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn replace_in_place_matches_replace() {
+        let cases: [(&[u8], &[u8], &[u8]); 4] = [
+            (b"aaaaa", b"aa", b"b"),
+            (b"xaay", b"a", b""),
+            (b"abcabc", b"abc", b"abc"),
+            (b"", b"ab", b"a"),
+        ];
+
+        for (slice, sequence, replacement) in cases {
+            let mut vec = slice.to_vec();
+            let count = vec.replace_seq_in_place(sequence, replacement);
+
+            assert_eq!(vec, slice.replace_seq(sequence, replacement));
+            assert_eq!(count, slice.find_iter(sequence).count());
+        }
+    }
 }
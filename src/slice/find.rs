@@ -0,0 +1,465 @@
+use core::cmp::{Ordering, max, min};
+
+const WORD: usize = size_of::<usize>();
+const LO: usize = usize::from_ne_bytes([0x01; WORD]);
+const HI: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// Checks if any byte of `word` is zero.
+const fn has_zero_byte(word: usize) -> bool {
+    word.wrapping_sub(LO) & !word & HI != 0
+}
+
+/// Gets the index of the first occurence of `needle` in `haystack`. The haystack is scanned a word
+/// at a time, which makes this much faster than `iter().position(..)` for long haystacks.
+/// # Example
+/// ```
+/// # use wutil::slice::memchr;
+/// assert_eq!(memchr(b'o', b"hello world"), Some(4));
+/// assert_eq!(memchr(b'z', b"hello world"), None);
+/// ```
+pub fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let repeated = LO * usize::from(needle);
+    let mut words = haystack.chunks_exact(WORD);
+
+    for (i, word) in words.by_ref().enumerate() {
+        let word = usize::from_ne_bytes(word.try_into().unwrap());
+
+        if has_zero_byte(word ^ repeated) {
+            let start = i * WORD;
+            return haystack[start..start + WORD]
+                .iter()
+                .position(|&b| b == needle)
+                .map(|offset| start + offset);
+        }
+    }
+
+    let start = haystack.len() - words.remainder().len();
+    words
+        .remainder()
+        .iter()
+        .position(|&b| b == needle)
+        .map(|offset| start + offset)
+}
+
+/// Gets the index of the last occurence of `needle` in `haystack`. Like [`memchr`], this scans
+/// the haystack a word at a time.
+/// # Example
+/// ```
+/// # use wutil::slice::memrchr;
+/// assert_eq!(memrchr(b'o', b"hello world"), Some(7));
+/// assert_eq!(memrchr(b'z', b"hello world"), None);
+/// ```
+pub fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let repeated = LO * usize::from(needle);
+    let mut words = haystack.rchunks_exact(WORD);
+
+    for (i, word) in words.by_ref().enumerate() {
+        let word = usize::from_ne_bytes(word.try_into().unwrap());
+
+        if has_zero_byte(word ^ repeated) {
+            let start = haystack.len() - (i + 1) * WORD;
+            return haystack[start..start + WORD]
+                .iter()
+                .rposition(|&b| b == needle)
+                .map(|offset| start + offset);
+        }
+    }
+
+    words.remainder().iter().rposition(|&b| b == needle)
+}
+
+/// A precomputed searcher for finding a needle in many haystacks.
+///
+/// This uses the [Two-Way](https://en.wikipedia.org/wiki/Two-way_string-matching_algorithm)
+/// algorithm, which runs in linear time and constant space. Preprocessing the needle takes linear
+/// time as well since elements are ordered with [`Ord`]. [`SliceExt::find`] uses the same
+/// algorithm for elements that are only [`PartialEq`], but preprocesses the needle on every call.
+///
+/// [`SliceExt::find`]: super::SliceExt::find
+///
+/// Byte needles can be prepared with [`Finder::new_bytes`] instead, which searches for
+/// single-byte needles with [`memchr`].
+/// # Example
+/// ```
+/// # use wutil::slice::Finder;
+/// let finder = Finder::new(b"needle");
+///
+/// assert_eq!(finder.find(b"haystack with a needle in it"), Some(16));
+/// assert_eq!(finder.find(b"haystack without one"), None);
+/// assert_eq!(Finder::new(b"").find(b"abc"), Some(0));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Finder<'n, T> {
+    needle: &'n [T],
+    factorization: Factorization,
+    /// The needle and a conversion of the haystack to bytes, which are only known when the
+    /// finder was created by [`Finder::new_bytes`] with a single-byte needle.
+    byte: Option<(u8, AsBytes<T>)>,
+}
+
+type AsBytes<T> = fn(&[T]) -> &[u8];
+
+impl<'n, T: Ord> Finder<'n, T> {
+    pub fn new(needle: &'n [T]) -> Self {
+        Self {
+            needle,
+            factorization: Factorization::new::<false, _>(needle, |a, b| needle[a].cmp(&needle[b])),
+            byte: None,
+        }
+    }
+}
+
+impl<'n> Finder<'n, u8> {
+    /// Prepares a byte needle like [`Finder::new`]. If the needle is a single byte, it is searched
+    /// for with [`memchr`] instead of Two-Way.
+    /// # Example
+    /// ```
+    /// # use wutil::slice::Finder;
+    /// let finder = Finder::new_bytes(b"!");
+    ///
+    /// assert_eq!(finder.find(b"hello, world!"), Some(12));
+    /// ```
+    pub fn new_bytes(needle: &'n [u8]) -> Self {
+        Self {
+            byte: match needle {
+                [byte] => Some((*byte, |haystack| haystack)),
+                _ => None,
+            },
+            ..Self::new(needle)
+        }
+    }
+}
+
+impl<'n, T: PartialEq> Finder<'n, T> {
+    /// Gets the needle that this searches for.
+    pub fn needle(&self) -> &'n [T] {
+        self.needle
+    }
+
+    /// Gets the index of the first occurence of the needle in `haystack`.
+    pub fn find(&self, haystack: &[T]) -> Option<usize> {
+        if let Some((byte, as_bytes)) = self.byte {
+            return memchr(byte, as_bytes(haystack));
+        }
+
+        self.factorization.find::<false, _>(self.needle, haystack)
+    }
+}
+
+/// A needle prepared for searching in both directions, so that iterators over matches only
+/// preprocess it once.
+#[derive(Clone, Debug)]
+pub(super) struct Searcher<'n, T> {
+    needle: &'n [T],
+    forward: Factorization,
+    backward: Factorization,
+}
+
+impl<'n, T: PartialEq> Searcher<'n, T> {
+    pub(super) fn new(needle: &'n [T]) -> Self {
+        Self {
+            needle,
+            forward: Factorization::new::<false, _>(
+                needle,
+                first_occurence_order::<false, _>(needle),
+            ),
+            backward: Factorization::new::<true, _>(
+                needle,
+                first_occurence_order::<true, _>(needle),
+            ),
+        }
+    }
+
+    pub(super) fn needle(&self) -> &'n [T] {
+        self.needle
+    }
+
+    pub(super) fn find(&self, haystack: &[T]) -> Option<usize> {
+        self.forward.find::<false, _>(self.needle, haystack)
+    }
+
+    pub(super) fn rfind(&self, haystack: &[T]) -> Option<usize> {
+        let end = self.backward.find::<true, _>(self.needle, haystack)?;
+        Some(haystack.len() - end - self.needle.len())
+    }
+}
+
+pub(super) fn find<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+    Factorization::new::<false, _>(needle, first_occurence_order::<false, _>(needle))
+        .find::<false, _>(needle, haystack)
+}
+
+pub(super) fn rfind<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+    let end = Factorization::new::<true, _>(needle, first_occurence_order::<true, _>(needle))
+        .find::<true, _>(needle, haystack)?;
+    Some(haystack.len() - end - needle.len())
+}
+
+/// A critical factorization of a needle, which lets the Two-Way algorithm skip ahead after a
+/// mismatch. When searching in reverse, indices are counted from the back of the needle and
+/// haystack.
+#[derive(Clone, Copy, Debug)]
+struct Factorization {
+    /// Where the needle is split into a left and right part
+    crit_pos: usize,
+    /// The period of the needle, or a lower bound of it for long periods
+    period: usize,
+    /// Whether the needle is not periodic, in which case the prefix that matched is not reused
+    long_period: bool,
+}
+
+impl Factorization {
+    /// Factorizes `needle`, where `cmp` orders the elements at two indices of the needle.
+    fn new<const REV: bool, T: PartialEq>(
+        needle: &[T],
+        cmp: impl Fn(usize, usize) -> Ordering,
+    ) -> Self {
+        let (crit_pos_less, period_less) = maximal_suffix(needle.len(), &cmp, Ordering::Less);
+        let (crit_pos_greater, period_greater) =
+            maximal_suffix(needle.len(), &cmp, Ordering::Greater);
+
+        let (crit_pos, period) = if crit_pos_less > crit_pos_greater {
+            (crit_pos_less, period_less)
+        } else {
+            (crit_pos_greater, period_greater)
+        };
+
+        let periodic = period + crit_pos <= needle.len()
+            && (0..crit_pos).all(|i| at::<REV, _>(needle, i) == at::<REV, _>(needle, i + period));
+
+        if periodic {
+            Self {
+                crit_pos,
+                period,
+                long_period: false,
+            }
+        } else {
+            Self {
+                crit_pos,
+                period: max(crit_pos, needle.len() - crit_pos) + 1,
+                long_period: true,
+            }
+        }
+    }
+
+    /// Gets the index of the first occurence of `needle` in `haystack`.
+    fn find<const REV: bool, T: PartialEq>(self, needle: &[T], haystack: &[T]) -> Option<usize> {
+        // A single element can't partially match, so a plain scan is faster
+        if let [element] = needle {
+            return if REV {
+                let index = haystack.iter().rposition(|e| e == element)?;
+                Some(haystack.len() - 1 - index)
+            } else {
+                haystack.iter().position(|e| e == element)
+            };
+        }
+
+        let mut pos = 0;
+        // The length of the needle prefix that is known to match at `pos`
+        let mut memory = 0;
+
+        while pos + needle.len() <= haystack.len() {
+            let matches = |i: usize| at::<REV, _>(needle, i) == at::<REV, _>(haystack, pos + i);
+
+            let start = max(self.crit_pos, memory);
+            if let Some(i) = (start..needle.len()).find(|&i| !matches(i)) {
+                pos += i - self.crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            let left = min(memory, self.crit_pos);
+            if (left..self.crit_pos).all(matches) {
+                return Some(pos);
+            }
+
+            pos += self.period;
+            if !self.long_period {
+                memory = needle.len() - self.period;
+            }
+        }
+
+        None
+    }
+}
+
+/// Gets the element at `index`, counting from the back if `REV` is set.
+fn at<const REV: bool, T>(slice: &[T], index: usize) -> &T {
+    if REV {
+        &slice[slice.len() - 1 - index]
+    } else {
+        &slice[index]
+    }
+}
+
+/// Orders the elements of `needle` by where they first occur in it. This is a total order that
+/// only needs [`PartialEq`], but comparing unequal elements takes `O(m)` time.
+fn first_occurence_order<const REV: bool, T: PartialEq>(
+    needle: &[T],
+) -> impl Fn(usize, usize) -> Ordering + '_ {
+    let first = move |i: usize| {
+        (0..i)
+            .find(|&j| at::<REV, _>(needle, j) == at::<REV, _>(needle, i))
+            .unwrap_or(i)
+    };
+
+    move |a, b| {
+        if at::<REV, _>(needle, a) == at::<REV, _>(needle, b) {
+            Ordering::Equal
+        } else {
+            first(a).cmp(&first(b))
+        }
+    }
+}
+
+/// Computes the start and period of the maximal suffix of a needle of length `len`, where suffixes
+/// are ordered by `order`.
+fn maximal_suffix(
+    len: usize,
+    cmp: impl Fn(usize, usize) -> Ordering,
+    order: Ordering,
+) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+
+    while right + offset < len {
+        match cmp(right + offset, left + offset) {
+            Ordering::Equal if offset + 1 == period => {
+                right += offset + 1;
+                offset = 0;
+            }
+            Ordering::Equal => offset += 1,
+            ord if ord == order => {
+                right += offset + 1;
+                offset = 0;
+                period = right - left;
+            }
+            _ => {
+                left = right;
+                right += 1;
+                offset = 0;
+                period = 1;
+            }
+        }
+    }
+
+    (left, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    /// Every string over `alphabet` with a length of up to `max_len`.
+    fn strings(alphabet: &[u8], max_len: usize) -> impl Iterator<Item = ([u8; 8], usize)> + '_ {
+        (0..=max_len).flat_map(move |len| {
+            (0..alphabet.len().pow(len as u32)).map(move |mut n| {
+                let mut s = [0; 8];
+                for c in &mut s[..len] {
+                    *c = alphabet[n % alphabet.len()];
+                    n /= alphabet.len();
+                }
+                (s, len)
+            })
+        })
+    }
+
+    #[test]
+    fn matches_naive() {
+        for (needle, needle_len) in strings(b"ab", 5) {
+            let needle = &needle[..needle_len];
+            let finder = Finder::new(needle);
+
+            for (haystack, haystack_len) in strings(b"abc", 7) {
+                let haystack = &haystack[..haystack_len];
+                let expected = naive(haystack, needle);
+
+                assert_eq!(finder.find(haystack), expected, "{needle:?} {haystack:?}");
+                assert_eq!(find(haystack, needle), expected, "{needle:?} {haystack:?}");
+
+                let expected = if needle.is_empty() {
+                    Some(haystack.len())
+                } else {
+                    haystack.windows(needle.len()).rposition(|w| w == needle)
+                };
+                assert_eq!(rfind(haystack, needle), expected, "{needle:?} {haystack:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn partial_eq_matches_naive() {
+        for (needle, needle_len) in strings(b"abc", 4) {
+            let needle = needle.map(f64::from);
+            let needle = &needle[..needle_len];
+            let searcher = Searcher::new(needle);
+
+            for (haystack, haystack_len) in strings(b"abc", 6) {
+                let haystack = haystack.map(f64::from);
+                let haystack = &haystack[..haystack_len];
+
+                let expected = naive(haystack, needle);
+                assert_eq!(find(haystack, needle), expected, "{needle:?} {haystack:?}");
+                assert_eq!(searcher.find(haystack), expected, "{needle:?} {haystack:?}");
+
+                let expected = if needle.is_empty() {
+                    Some(haystack.len())
+                } else {
+                    haystack.windows(needle.len()).rposition(|w| w == needle)
+                };
+                assert_eq!(rfind(haystack, needle), expected, "{needle:?} {haystack:?}");
+                assert_eq!(
+                    searcher.rfind(haystack),
+                    expected,
+                    "{needle:?} {haystack:?}"
+                );
+            }
+        }
+
+        assert_eq!(find(&[1.0, f64::NAN, 2.0], &[f64::NAN]), None);
+        assert_eq!(find(&[1.0, f64::NAN, 2.0], &[2.0]), Some(2));
+    }
+
+    #[test]
+    fn single_byte_finder() {
+        for (haystack, len) in strings(b"abc", 6) {
+            let haystack = &haystack[..len];
+
+            for needle in [b"a", b"c"] {
+                let expected = naive(haystack, needle);
+                assert_eq!(Finder::new_bytes(needle).find(haystack), expected);
+            }
+        }
+
+        assert_eq!(Finder::new_bytes(b"bc").find(b"abc"), Some(1));
+        assert_eq!(Finder::new_bytes(b"").find(b"abc"), Some(0));
+    }
+
+    #[test]
+    fn memchr_every_position() {
+        let mut haystack = [0u8; 67];
+
+        for i in 0..haystack.len() {
+            haystack[i] = 1;
+            for start in 0..=i {
+                assert_eq!(memchr(1, &haystack[start..]), Some(i - start));
+            }
+            for end in i + 1..=haystack.len() {
+                assert_eq!(memrchr(1, &haystack[..end]), Some(i));
+            }
+            assert_eq!(memchr(1, &haystack[i + 1..]), None);
+            assert_eq!(memrchr(1, &haystack[..i]), None);
+            haystack[i] = 0x81;
+        }
+    }
+}
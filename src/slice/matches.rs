@@ -0,0 +1,318 @@
+use core::iter::FusedIterator;
+
+use super::find::Searcher;
+use crate::span::Span;
+
+/// Iterator over the starting indices of the non-overlapping occurences of a subslice, searching
+/// from the front.
+///
+/// Like [`str::matches`] with a string pattern, this isn't double-ended: when the subslice can
+/// overlap itself, searching from the back finds different matches (`aa` in `aaa` is found at `0`
+/// from the front but at `1` from the back). Use [`RFindIter`] to search from the back instead.
+///
+/// This `struct` is created by [`SliceExt::find_iter`](super::SliceExt::find_iter).
+#[derive(Clone, Debug)]
+pub struct FindIter<'a, T>
+where
+    T: PartialEq,
+{
+    haystack: &'a [T],
+    searcher: Searcher<'a, T>,
+    /// The start of the remaining haystack, or `None` when finished
+    pos: Option<usize>,
+}
+
+impl<'a, T> FindIter<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], needle: &'a [T]) -> Self {
+        Self {
+            haystack,
+            searcher: Searcher::new(needle),
+            pos: Some(0),
+        }
+    }
+}
+
+impl<T> Iterator for FindIter<'_, T>
+where
+    T: PartialEq,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.pos?;
+        let Some(index) = self.searcher.find(&self.haystack[pos..]).map(|i| pos + i) else {
+            self.pos = None;
+            return None;
+        };
+
+        // Empty needles match at every index, including the end of the haystack
+        let next = index + self.searcher.needle().len().max(1);
+        self.pos = (next <= self.haystack.len()).then_some(next);
+
+        Some(index)
+    }
+}
+
+impl<T> FusedIterator for FindIter<'_, T> where T: PartialEq {}
+
+/// Iterator over the starting indices of the non-overlapping occurences of a subslice, searching
+/// from the back. For the same reason as [`FindIter`], this isn't double-ended.
+///
+/// This `struct` is created by [`SliceExt::rfind_iter`](super::SliceExt::rfind_iter).
+#[derive(Clone, Debug)]
+pub struct RFindIter<'a, T>
+where
+    T: PartialEq,
+{
+    haystack: &'a [T],
+    searcher: Searcher<'a, T>,
+    /// The end of the remaining haystack, or `None` when finished
+    end: Option<usize>,
+}
+
+impl<'a, T> RFindIter<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], needle: &'a [T]) -> Self {
+        Self {
+            haystack,
+            searcher: Searcher::new(needle),
+            end: Some(haystack.len()),
+        }
+    }
+}
+
+impl<T> Iterator for RFindIter<'_, T>
+where
+    T: PartialEq,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+        let Some(index) = self.searcher.rfind(&self.haystack[..end]) else {
+            self.end = None;
+            return None;
+        };
+
+        self.end = if self.searcher.needle().is_empty() {
+            index.checked_sub(1)
+        } else {
+            Some(index)
+        };
+
+        Some(index)
+    }
+}
+
+impl<T> FusedIterator for RFindIter<'_, T> where T: PartialEq {}
+
+/// Iterator over the starting indices of every occurence of a subslice, including overlapping
+/// ones.
+///
+/// This `struct` is created by
+/// [`SliceExt::find_overlapping_iter`](super::SliceExt::find_overlapping_iter).
+#[derive(Clone, Debug)]
+pub struct FindOverlappingIter<'a, T>
+where
+    T: PartialEq,
+{
+    haystack: &'a [T],
+    searcher: Searcher<'a, T>,
+    /// The indices that a remaining match could start at
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> FindOverlappingIter<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], needle: &'a [T]) -> Self {
+        Self {
+            haystack,
+            searcher: Searcher::new(needle),
+            front: 0,
+            back: (haystack.len() + 1).saturating_sub(needle.len()),
+        }
+    }
+
+    /// The part of the haystack that the remaining matches are in.
+    fn remaining(&self) -> &'a [T] {
+        &self.haystack[self.front..self.back + self.searcher.needle().len() - 1]
+    }
+}
+
+impl<T> Iterator for FindOverlappingIter<'_, T>
+where
+    T: PartialEq,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let Some(index) = self.searcher.find(self.remaining()).map(|i| self.front + i) else {
+            self.front = self.back;
+            return None;
+        };
+
+        self.front = index + 1;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.back.saturating_sub(self.front)))
+    }
+}
+
+impl<T> DoubleEndedIterator for FindOverlappingIter<'_, T>
+where
+    T: PartialEq,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let Some(index) = self
+            .searcher
+            .rfind(self.remaining())
+            .map(|i| self.front + i)
+        else {
+            self.back = self.front;
+            return None;
+        };
+
+        self.back = index;
+        Some(index)
+    }
+}
+
+impl<T> FusedIterator for FindOverlappingIter<'_, T> where T: PartialEq {}
+
+/// Iterator over the spans of the non-overlapping occurences of a subslice, searching from the
+/// front. For the same reason as [`FindIter`], this isn't double-ended.
+///
+/// This `struct` is created by [`SliceExt::match_spans`](super::SliceExt::match_spans).
+#[derive(Clone, Debug)]
+pub struct MatchSpans<'a, T>
+where
+    T: PartialEq,
+{
+    inner: FindIter<'a, T>,
+}
+
+impl<'a, T> MatchSpans<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], needle: &'a [T]) -> Self {
+        Self {
+            inner: FindIter::new(haystack, needle),
+        }
+    }
+}
+
+impl<T> Iterator for MatchSpans<'_, T>
+where
+    T: PartialEq,
+{
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.inner.next()?;
+
+        Some(Span::at(start).with_len(self.inner.searcher.needle().len()))
+    }
+}
+
+impl<T> FusedIterator for MatchSpans<'_, T> where T: PartialEq {}
+
+/// Iterator over the spans of the non-overlapping occurences of a subslice, searching from the
+/// back.
+///
+/// This `struct` is created by [`SliceExt::rmatch_spans`](super::SliceExt::rmatch_spans).
+#[derive(Clone, Debug)]
+pub struct RMatchSpans<'a, T>
+where
+    T: PartialEq,
+{
+    inner: RFindIter<'a, T>,
+}
+
+impl<'a, T> RMatchSpans<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], needle: &'a [T]) -> Self {
+        Self {
+            inner: RFindIter::new(haystack, needle),
+        }
+    }
+}
+
+impl<T> Iterator for RMatchSpans<'_, T>
+where
+    T: PartialEq,
+{
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.inner.next()?;
+
+        Some(Span::at(start).with_len(self.inner.searcher.needle().len()))
+    }
+}
+
+impl<T> FusedIterator for RMatchSpans<'_, T> where T: PartialEq {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{prelude::*, span::Span};
+
+    #[test]
+    fn empty_needle() {
+        let haystack = b"abc";
+
+        assert!(haystack.find_iter(b"").eq(0..=3));
+        assert!(haystack.rfind_iter(b"").eq((0..=3).rev()));
+        assert!(haystack.find_overlapping_iter(b"").eq(0..=3));
+        assert!(haystack.find_overlapping_iter(b"").rev().eq((0..=3).rev()));
+        assert!(b"".find_iter(b"").eq([0]));
+    }
+
+    #[test]
+    fn match_spans_from_both_ends() {
+        let haystack = b"aaa";
+
+        assert!(haystack.match_spans(b"aa").eq([Span::from(0..2)]));
+        assert!(haystack.rmatch_spans(b"aa").eq([Span::from(1..3)]));
+        assert!(haystack.rmatch_spans(b"").eq((0..=3).rev().map(Span::at)));
+    }
+
+    #[test]
+    fn overlapping_from_both_ends() {
+        let haystack = b"aaaabaaa";
+        let expected = [0, 1, 2, 5, 6];
+
+        for split in 0..=expected.len() {
+            let mut iter = haystack.find_overlapping_iter(b"aa");
+            let mut front: Vec<usize> = iter.by_ref().take(split).collect();
+            let back: Vec<usize> = iter.rev().collect();
+
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, expected);
+        }
+
+        assert_eq!(b"a".find_overlapping_iter(b"aa").next(), None);
+    }
+}
@@ -0,0 +1,512 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::iter::FusedIterator;
+
+use crate::span::Span;
+
+/// Decides which match [`MultiFinder`] reports when several patterns match.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MatchKind {
+    /// Report the match that starts first. If several matches start at the same index, report the
+    /// one whose pattern was given first.
+    #[default]
+    LeftmostFirst,
+    /// Report the match that starts first. If several matches start at the same index, report the
+    /// longest one.
+    LeftmostLongest,
+}
+
+#[derive(Clone, Debug)]
+struct Node<'p, T> {
+    transitions: Vec<(&'p T, usize)>,
+    /// The node of the longest proper suffix of this node that is in the trie
+    fail: usize,
+    depth: usize,
+    /// The patterns that end at this node
+    patterns: Vec<usize>,
+    /// The closest node in the chain of failure links that some pattern ends at
+    output: Option<usize>,
+}
+
+impl<T> Node<'_, T> {
+    fn new(depth: usize) -> Self {
+        Self {
+            transitions: Vec::new(),
+            fail: 0,
+            depth,
+            patterns: Vec::new(),
+            output: None,
+        }
+    }
+}
+
+/// A searcher for finding many patterns at once, built with the
+/// [Aho-Corasick](https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm) algorithm.
+///
+/// Matches are reported as the index of the pattern that matched along with the span of the match.
+/// # Example
+/// ```
+/// # use wutil::slice::{MatchKind, MultiFinder};
+/// # use wutil::span::Span;
+/// let patterns: [&[u8]; 3] = [b"Sam", b"Samwise", b"wise"];
+/// let haystack = b"Samwise is wise";
+///
+/// let finder = MultiFinder::new(&patterns);
+/// let matches: Vec<(usize, Span)> = finder.find_iter(haystack).collect();
+/// assert_eq!(matches, [(0, Span::from(0..3)), (2, Span::from(3..7)), (2, Span::from(11..15))]);
+///
+/// let finder = finder.with_match_kind(MatchKind::LeftmostLongest);
+/// assert_eq!(finder.find(haystack), Some((1, Span::from(0..7))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiFinder<'p, T> {
+    nodes: Vec<Node<'p, T>>,
+    pattern_lens: Vec<usize>,
+    match_kind: MatchKind,
+}
+
+impl<'p, T> MultiFinder<'p, T>
+where
+    T: PartialEq,
+{
+    pub fn new(patterns: &[&'p [T]]) -> Self {
+        let mut nodes = vec![Node::new(0)];
+
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut node = 0;
+
+            for element in *pattern {
+                node = match nodes[node].transitions.iter().find(|(e, _)| *e == element) {
+                    Some(&(_, next)) => next,
+                    None => {
+                        let next = nodes.len();
+                        nodes.push(Node::new(nodes[node].depth + 1));
+                        nodes[node].transitions.push((element, next));
+                        next
+                    }
+                };
+            }
+
+            nodes[node].patterns.push(id);
+        }
+
+        let mut finder = Self {
+            nodes,
+            pattern_lens: patterns.iter().map(|p| p.len()).collect(),
+            match_kind: MatchKind::default(),
+        };
+
+        finder.build_failure_links();
+        finder
+    }
+
+    fn build_failure_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[0].transitions.iter().map(|t| t.1).collect();
+        let root_output = (!self.nodes[0].patterns.is_empty()).then_some(0);
+
+        for &child in &queue {
+            self.nodes[child].output = root_output;
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for i in 0..self.nodes[node].transitions.len() {
+                let (element, child) = self.nodes[node].transitions[i];
+                let fail = self.next_state(self.nodes[node].fail, element);
+
+                self.nodes[child].fail = fail;
+                self.nodes[child].output = if self.nodes[fail].patterns.is_empty() {
+                    self.nodes[fail].output
+                } else {
+                    Some(fail)
+                };
+
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Sets how overlapping matches are chosen between. The default is
+    /// [`MatchKind::LeftmostFirst`].
+    pub fn with_match_kind(mut self, match_kind: MatchKind) -> Self {
+        self.match_kind = match_kind;
+        self
+    }
+
+    /// The number of patterns that this searches for.
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    fn next_state(&self, mut state: usize, element: &T) -> usize {
+        loop {
+            let node = &self.nodes[state];
+
+            if let Some(&(_, next)) = node.transitions.iter().find(|(e, _)| *e == element) {
+                return next;
+            }
+
+            if state == 0 {
+                return 0;
+            }
+
+            state = node.fail;
+        }
+    }
+
+    /// Calls `f` with every match that ends at `end` in `state`.
+    fn for_each_match(&self, state: usize, end: usize, mut f: impl FnMut(usize, Span)) {
+        let mut node = Some(state);
+
+        while let Some(n) = node {
+            for &pattern in &self.nodes[n].patterns {
+                f(pattern, Span::from(end - self.pattern_lens[pattern]..end));
+            }
+
+            node = self.nodes[n].output;
+        }
+    }
+
+    /// Checks if `candidate` should be reported instead of `best`.
+    fn is_better(&self, candidate: (usize, Span), best: Option<(usize, Span)>) -> bool {
+        let (pattern, span) = candidate;
+        let Some((best_pattern, best_span)) = best else {
+            return true;
+        };
+
+        span.start < best_span.start
+            || span.start == best_span.start
+                && match self.match_kind {
+                    MatchKind::LeftmostFirst => pattern < best_pattern,
+                    MatchKind::LeftmostLongest => {
+                        (span.len(), best_pattern) > (best_span.len(), pattern)
+                    }
+                }
+    }
+
+    /// Finds the first match that starts at or after `start` according to the match kind.
+    pub(super) fn find_at(&self, haystack: &[T], start: usize) -> Option<(usize, Span)> {
+        LeftmostSearch::new(start).find(self, haystack)
+    }
+
+    /// Finds the first match in `haystack`.
+    pub fn find(&self, haystack: &[T]) -> Option<(usize, Span)> {
+        self.find_at(haystack, 0)
+    }
+
+    /// Returns an iterator over the non-overlapping matches in `haystack`.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h [T]) -> MultiFindIter<'a, 'p, 'h, T> {
+        MultiFindIter {
+            finder: self,
+            haystack,
+            search: Some(LeftmostSearch::new(0)),
+        }
+    }
+
+    /// Returns an iterator over every match in `haystack`, including overlapping ones. Matches are
+    /// ordered by where they end. This ignores the match kind.
+    /// # Example
+    /// ```
+    /// # use wutil::slice::MultiFinder;
+    /// # use wutil::span::Span;
+    /// let patterns: [&[u8]; 2] = [b"abc", b"b"];
+    /// let finder = MultiFinder::new(&patterns);
+    ///
+    /// let matches: Vec<(usize, Span)> = finder.find_overlapping_iter(b"abc").collect();
+    /// assert_eq!(matches, [(1, Span::from(1..2)), (0, Span::from(0..3))]);
+    /// ```
+    pub fn find_overlapping_iter<'a, 'h>(
+        &'a self,
+        haystack: &'h [T],
+    ) -> MultiFindOverlappingIter<'a, 'p, 'h, T> {
+        MultiFindOverlappingIter {
+            finder: self,
+            haystack,
+            state: 0,
+            end: 0,
+            output: Some(0),
+            pattern: 0,
+        }
+    }
+
+    /// Replaces every non-overlapping match with the replacement at the index of its pattern.
+    /// # Panics
+    /// Panics if there are fewer replacements than patterns.
+    /// # Example
+    /// ```
+    /// # use wutil::slice::MultiFinder;
+    /// let patterns: [&[u8]; 2] = [b"cat", b"dog"];
+    /// let finder = MultiFinder::new(&patterns);
+    ///
+    /// let replaced = finder.replace_all(b"dog eat cat", &[b"dog", b"cat"]);
+    /// assert_eq!(replaced, b"cat eat dog");
+    /// ```
+    pub fn replace_all(&self, haystack: &[T], replacements: &[&[T]]) -> Vec<T>
+    where
+        T: Clone,
+    {
+        assert!(
+            replacements.len() >= self.pattern_count(),
+            "expected {} replacements, got {}",
+            self.pattern_count(),
+            replacements.len()
+        );
+
+        let mut replaced = Vec::with_capacity(haystack.len());
+        let mut last = 0;
+
+        for (pattern, span) in self.find_iter(haystack) {
+            replaced.extend_from_slice(&haystack[last..span.start]);
+            replaced.extend_from_slice(replacements[pattern]);
+            last = span.end;
+        }
+
+        replaced.extend_from_slice(&haystack[last..]);
+        replaced
+    }
+}
+
+/// A search for the first match after some index, which can be resumed after more of the haystack
+/// becomes relevant. This is kept by iterators so that the automaton is only restarted once a match
+/// is consumed.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct LeftmostSearch {
+    state: usize,
+    /// The number of elements that have been searched
+    end: usize,
+    /// The leftmost match found so far
+    best: Option<(usize, Span)>,
+    /// Whether no later match can be better than `best`
+    finished: bool,
+}
+
+impl LeftmostSearch {
+    pub(super) fn new(start: usize) -> Self {
+        Self {
+            state: 0,
+            end: start,
+            best: None,
+            finished: false,
+        }
+    }
+
+    /// Searches until the first match is known. Calling this again returns the same match without
+    /// searching.
+    pub(super) fn find<T: PartialEq>(
+        &mut self,
+        finder: &MultiFinder<'_, T>,
+        haystack: &[T],
+    ) -> Option<(usize, Span)> {
+        while !self.finished {
+            finder.for_each_match(self.state, self.end, |pattern, span| {
+                if finder.is_better((pattern, span), self.best) {
+                    self.best = Some((pattern, span));
+                }
+            });
+
+            // Later matches start at or after the start of the prefix that the state represents
+            let prefix_start = self.end - finder.nodes[self.state].depth;
+            if self.best.is_some_and(|(_, span)| span.start < prefix_start) {
+                self.finished = true;
+                break;
+            }
+
+            let Some(element) = haystack.get(self.end) else {
+                self.finished = true;
+                break;
+            };
+
+            self.state = finder.next_state(self.state, element);
+            self.end += 1;
+        }
+
+        self.best
+    }
+}
+
+/// Iterator over the non-overlapping matches of a [`MultiFinder`].
+///
+/// This `struct` is created by [`MultiFinder::find_iter`].
+#[derive(Clone, Debug)]
+pub struct MultiFindIter<'a, 'p, 'h, T> {
+    finder: &'a MultiFinder<'p, T>,
+    haystack: &'h [T],
+    /// The search for the next match, or `None` when finished
+    search: Option<LeftmostSearch>,
+}
+
+impl<T> Iterator for MultiFindIter<'_, '_, '_, T>
+where
+    T: PartialEq,
+{
+    type Item = (usize, Span);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let search = self.search.as_mut()?;
+        let Some((pattern, span)) = search.find(self.finder, self.haystack) else {
+            self.search = None;
+            return None;
+        };
+
+        // Empty matches can't be followed by another match at the same index
+        let next = span.end + usize::from(span.is_empty());
+        self.search = (next <= self.haystack.len()).then(|| LeftmostSearch::new(next));
+
+        Some((pattern, span))
+    }
+}
+
+impl<T> FusedIterator for MultiFindIter<'_, '_, '_, T> where T: PartialEq {}
+
+/// Iterator over every match of a [`MultiFinder`], including overlapping ones.
+///
+/// This `struct` is created by [`MultiFinder::find_overlapping_iter`].
+#[derive(Clone, Debug)]
+pub struct MultiFindOverlappingIter<'a, 'p, 'h, T> {
+    finder: &'a MultiFinder<'p, T>,
+    haystack: &'h [T],
+    state: usize,
+    /// The number of elements that have been searched
+    end: usize,
+    /// The node whose patterns are being reported
+    output: Option<usize>,
+    /// The index of the next pattern to report in `output`
+    pattern: usize,
+}
+
+impl<T> Iterator for MultiFindOverlappingIter<'_, '_, '_, T>
+where
+    T: PartialEq,
+{
+    type Item = (usize, Span);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nodes = &self.finder.nodes;
+
+        loop {
+            if let Some(node) = self.output {
+                if let Some(&pattern) = nodes[node].patterns.get(self.pattern) {
+                    self.pattern += 1;
+
+                    let len = self.finder.pattern_lens[pattern];
+                    return Some((pattern, Span::from(self.end - len..self.end)));
+                }
+
+                self.output = nodes[node].output;
+                self.pattern = 0;
+                continue;
+            }
+
+            let element = self.haystack.get(self.end)?;
+            self.state = self.finder.next_state(self.state, element);
+            self.end += 1;
+            self.output = Some(self.state);
+        }
+    }
+}
+
+impl<T> FusedIterator for MultiFindOverlappingIter<'_, '_, '_, T> where T: PartialEq {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Finds the leftmost match by trying every pattern at every index.
+    fn naive(patterns: &[&[u8]], haystack: &[u8], kind: MatchKind) -> Option<(usize, Span)> {
+        (0..=haystack.len()).find_map(|start| {
+            let matching = patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| haystack[start..].starts_with(p));
+
+            let (id, pattern) = match kind {
+                MatchKind::LeftmostFirst => matching.min_by_key(|(id, _)| *id),
+                MatchKind::LeftmostLongest => {
+                    matching.min_by_key(|(id, p)| (core::cmp::Reverse(p.len()), *id))
+                }
+            }?;
+
+            Some((id, Span::at(start).with_len(pattern.len())))
+        })
+    }
+
+    #[test]
+    fn matches_naive() {
+        let patterns: [&[u8]; 7] = [b"ab", b"b", b"abc", b"bcd", b"c", b"bcab", b"dab"];
+        let haystacks: [&[u8]; 6] = [b"", b"abcd", b"xbcabcdab", b"dabcab", b"cccc", b"dabca"];
+
+        for count in 0..=patterns.len() {
+            for haystack in haystacks {
+                for kind in [MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+                    let patterns = &patterns[..count];
+                    let finder = MultiFinder::new(patterns).with_match_kind(kind);
+
+                    for start in 0..=haystack.len() {
+                        let expected = naive(patterns, &haystack[start..], kind)
+                            .map(|(id, span)| (id, span + start));
+                        assert_eq!(finder.find_at(haystack, start), expected);
+                    }
+
+                    let mut expected = Vec::new();
+                    let mut start = 0;
+                    while let Some((id, span)) = naive(patterns, &haystack[start..], kind) {
+                        let span = span + start;
+                        expected.push((id, span));
+                        start = span.end + usize::from(span.is_empty());
+                        if start > haystack.len() {
+                            break;
+                        }
+                    }
+                    assert_eq!(finder.find_iter(haystack).collect::<Vec<_>>(), expected);
+
+                    let overlapping = finder.find_overlapping_iter(haystack).count();
+                    let expected: usize = patterns
+                        .iter()
+                        .map(|p| haystack.windows(p.len()).filter(|w| w == p).count())
+                        .sum();
+                    assert_eq!(overlapping, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_pattern() {
+        let patterns: [&[u8]; 2] = [b"a", b""];
+        let finder = MultiFinder::new(&patterns);
+
+        let matches: Vec<(usize, Span)> = finder.find_iter(b"ab").collect();
+        assert_eq!(
+            matches,
+            [(0, Span::from(0..1)), (1, Span::at(1)), (1, Span::at(2))]
+        );
+
+        let overlapping: Vec<(usize, Span)> = finder.find_overlapping_iter(b"ab").collect();
+        assert_eq!(
+            overlapping,
+            [
+                (1, Span::at(0)),
+                (0, Span::from(0..1)),
+                (1, Span::at(1)),
+                (1, Span::at(2))
+            ]
+        );
+    }
+
+    #[test]
+    fn sequences_removed_matches_single() {
+        use crate::prelude::*;
+
+        let cases: [(&[u8], &[u8]); 3] = [(b"aabb", b"ab"), (b"aaaaa", b"aa"), (b"abcab", b"ab")];
+
+        for (slice, sequence) in cases {
+            let single: Vec<&u8> = slice.with_sequence_removed(sequence).collect();
+            let many: Vec<&u8> = slice.with_sequences_removed(&[sequence]).collect();
+            assert_eq!(single, many);
+        }
+
+        let removed: Vec<u8> = b"abc"
+            .with_sequences_removed(&[b"b", b""])
+            .copied()
+            .collect();
+        assert_eq!(removed, b"ac");
+    }
+}
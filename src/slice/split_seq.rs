@@ -0,0 +1,240 @@
+use core::iter::FusedIterator;
+
+use super::matches::{FindIter, RFindIter};
+
+/// Iterator over the subslices separated by a delimiter sequence.
+///
+/// This `struct` is created by [`SliceExt::split_seq`](super::SliceExt::split_seq).
+#[derive(Clone, Debug)]
+pub struct SplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    haystack: &'a [T],
+    delimiter_len: usize,
+    matches: FindIter<'a, T>,
+    /// The start of the next subslice, or `None` when finished
+    start: Option<usize>,
+}
+
+impl<'a, T> SplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], delimiter: &'a [T]) -> Self {
+        Self {
+            haystack,
+            delimiter_len: delimiter.len(),
+            matches: FindIter::new(haystack, delimiter),
+            start: Some(0),
+        }
+    }
+
+    /// Gets the rest of the slice without splitting it and finishes the iterator.
+    fn take_remainder(&mut self) -> Option<&'a [T]> {
+        Some(&self.haystack[self.start.take()?..])
+    }
+}
+
+impl<'a, T> Iterator for SplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+
+        match self.matches.next() {
+            Some(index) => {
+                self.start = Some(index + self.delimiter_len);
+                Some(&self.haystack[start..index])
+            }
+            None => self.take_remainder(),
+        }
+    }
+}
+
+impl<T> FusedIterator for SplitSeq<'_, T> where T: PartialEq {}
+
+/// Iterator over the subslices separated by a delimiter sequence, where each subslice includes the
+/// delimiter that ends it.
+///
+/// This `struct` is created by
+/// [`SliceExt::split_seq_inclusive`](super::SliceExt::split_seq_inclusive).
+#[derive(Clone, Debug)]
+pub struct SplitSeqInclusive<'a, T>
+where
+    T: PartialEq,
+{
+    inner: SplitSeq<'a, T>,
+}
+
+impl<'a, T> SplitSeqInclusive<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], delimiter: &'a [T]) -> Self {
+        Self {
+            inner: SplitSeq::new(haystack, delimiter),
+        }
+    }
+}
+
+impl<'a, T> Iterator for SplitSeqInclusive<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = &mut self.inner;
+        let start = inner.start?;
+
+        match inner.matches.next() {
+            Some(index) => {
+                let end = index + inner.delimiter_len;
+                inner.start = Some(end);
+                Some(&inner.haystack[start..end])
+            }
+            // Like `str::split_inclusive`, a trailing delimiter does not start an empty subslice
+            None => inner.take_remainder().filter(|rest| !rest.is_empty()),
+        }
+    }
+}
+
+impl<T> FusedIterator for SplitSeqInclusive<'_, T> where T: PartialEq {}
+
+/// Iterator over the subslices separated by a delimiter sequence, limited to a number of
+/// subslices.
+///
+/// This `struct` is created by [`SliceExt::splitn_seq`](super::SliceExt::splitn_seq).
+#[derive(Clone, Debug)]
+pub struct SplitNSeq<'a, T>
+where
+    T: PartialEq,
+{
+    inner: SplitSeq<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T> SplitNSeq<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], delimiter: &'a [T], n: usize) -> Self {
+        Self {
+            inner: SplitSeq::new(haystack, delimiter),
+            remaining: n,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SplitNSeq<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            0 => None,
+            1 => {
+                self.remaining = 0;
+                self.inner.take_remainder()
+            }
+            _ => {
+                self.remaining -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for SplitNSeq<'_, T> where T: PartialEq {}
+
+/// Iterator over the subslices separated by a delimiter sequence, starting from the back.
+///
+/// This `struct` is created by [`SliceExt::rsplit_seq`](super::SliceExt::rsplit_seq).
+#[derive(Clone, Debug)]
+pub struct RSplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    haystack: &'a [T],
+    delimiter_len: usize,
+    matches: RFindIter<'a, T>,
+    /// The end of the next subslice, or `None` when finished
+    end: Option<usize>,
+}
+
+impl<'a, T> RSplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(haystack: &'a [T], delimiter: &'a [T]) -> Self {
+        Self {
+            haystack,
+            delimiter_len: delimiter.len(),
+            matches: RFindIter::new(haystack, delimiter),
+            end: Some(haystack.len()),
+        }
+    }
+}
+
+impl<'a, T> Iterator for RSplitSeq<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+
+        match self.matches.next() {
+            Some(index) => {
+                self.end = Some(index);
+                Some(&self.haystack[index + self.delimiter_len..end])
+            }
+            None => {
+                self.end = None;
+                Some(&self.haystack[..end])
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for RSplitSeq<'_, T> where T: PartialEq {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn empty_delimiter() {
+        let split: Vec<&[u8]> = b"ab".split_seq(b"").collect();
+        assert_eq!(split, [&b""[..], b"a", b"b", b""]);
+
+        let rsplit: Vec<&[u8]> = b"ab".rsplit_seq(b"").collect();
+        assert_eq!(rsplit, [&b""[..], b"b", b"a", b""]);
+    }
+
+    #[test]
+    fn empty_haystack() {
+        assert!(b"".split_seq(b"ab").eq([b""]));
+        assert!(b"".rsplit_seq(b"ab").eq([b""]));
+        assert_eq!(b"".split_seq_inclusive(b"ab").next(), None);
+        assert_eq!(b"".splitn_seq(0, b"ab").next(), None);
+    }
+
+    #[test]
+    fn overlapping_delimiters() {
+        let split: Vec<&[u8]> = b"xaaay".split_seq(b"aa").collect();
+        assert_eq!(split, [&b"x"[..], b"ay"]);
+
+        let rsplit: Vec<&[u8]> = b"xaaay".rsplit_seq(b"aa").collect();
+        assert_eq!(rsplit, [&b"y"[..], b"xa"]);
+    }
+}
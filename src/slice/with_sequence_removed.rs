@@ -1,3 +1,6 @@
+#[cfg(feature = "alloc")]
+use super::{MultiFinder, multi_find::LeftmostSearch};
+
 /// Iterator returned by [`SliceExt.with_sequence_removed(..)`].
 ///
 /// [`SliceExt.with_sequence_removed(..)`]: super::SliceExt::with_sequence_removed
@@ -5,8 +8,7 @@ pub struct WithSequenceRemoved<'a, T>
 where
     T: PartialEq,
 {
-    sequence: &'a [T],
-    remaining: &'a [T],
+    inner: WithSequenceReplaced<'a, T>,
 }
 
 impl<'a, T> WithSequenceRemoved<'a, T>
@@ -15,8 +17,7 @@ where
 {
     pub(super) fn new(slice: &'a [T], sequence: &'a [T]) -> Self {
         Self {
-            remaining: slice,
-            sequence,
+            inner: WithSequenceReplaced::new(slice, sequence, &[], usize::MAX),
         }
     }
 }
@@ -27,17 +28,143 @@ where
 {
     type Item = &'a T;
 
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterator returned by [`SliceExt.with_sequence_replaced(..)`] and
+/// [`SliceExt.with_sequence_replacedn(..)`].
+///
+/// [`SliceExt.with_sequence_replaced(..)`]: super::SliceExt::with_sequence_replaced
+/// [`SliceExt.with_sequence_replacedn(..)`]: super::SliceExt::with_sequence_replacedn
+pub struct WithSequenceReplaced<'a, T>
+where
+    T: PartialEq,
+{
+    sequence: &'a [T],
+    replacement: &'a [T],
+    remaining: &'a [T],
+    /// The part of the current replacement that hasn't been yielded yet
+    replacing: &'a [T],
+    replacements_left: usize,
+    /// Whether an empty sequence was already replaced before the next element
+    replaced_empty: bool,
+}
+
+impl<'a, T> WithSequenceReplaced<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(
+        slice: &'a [T],
+        sequence: &'a [T],
+        replacement: &'a [T],
+        limit: usize,
+    ) -> Self {
+        Self {
+            sequence,
+            replacement,
+            remaining: slice,
+            replacing: &[],
+            replacements_left: limit,
+            replaced_empty: false,
+        }
+    }
+}
+
+impl<'a, T> Iterator for WithSequenceReplaced<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a T;
+
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            if self.remaining.starts_with(self.sequence) {
-                self.remaining = self.remaining.get(self.sequence.len()..).unwrap_or(&[]);
+            if let Some((first_element, replacing)) = self.replacing.split_first() {
+                self.replacing = replacing;
+                return Some(first_element);
+            }
+
+            if self.replacements_left > 0
+                && !self.replaced_empty
+                && self.remaining.starts_with(self.sequence)
+            {
+                self.remaining = &self.remaining[self.sequence.len()..];
+                self.replacing = self.replacement;
+                self.replacements_left -= 1;
+                self.replaced_empty = self.sequence.is_empty();
                 continue;
             }
 
             let (first_element, remaining) = self.remaining.split_first()?;
             self.remaining = remaining;
+            self.replaced_empty = false;
 
             return Some(first_element);
         }
     }
 }
+
+/// Iterator returned by [`SliceExt.with_sequences_removed(..)`].
+///
+/// [`SliceExt.with_sequences_removed(..)`]: super::SliceExt::with_sequences_removed
+#[cfg(feature = "alloc")]
+pub struct WithSequencesRemoved<'a, T>
+where
+    T: PartialEq,
+{
+    finder: MultiFinder<'a, T>,
+    slice: &'a [T],
+    pos: usize,
+    /// The search for the first match at or after `pos`
+    search: LeftmostSearch,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> WithSequencesRemoved<'a, T>
+where
+    T: PartialEq,
+{
+    pub(super) fn new(slice: &'a [T], sequences: &[&'a [T]]) -> Self {
+        Self {
+            finder: MultiFinder::new(sequences),
+            slice,
+            pos: 0,
+            search: LeftmostSearch::new(0),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for WithSequencesRemoved<'a, T>
+where
+    T: PartialEq,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((_, span)) = self
+            .search
+            .find(&self.finder, self.slice)
+            .filter(|(_, span)| span.start == self.pos && !span.is_empty())
+        {
+            self.pos = span.end;
+            self.search = LeftmostSearch::new(self.pos);
+        }
+
+        let element = self.slice.get(self.pos)?;
+        self.pos += 1;
+
+        // An empty match before the element is consumed along with it
+        if self
+            .search
+            .find(&self.finder, self.slice)
+            .is_some_and(|(_, span)| span.start < self.pos)
+        {
+            self.search = LeftmostSearch::new(self.pos);
+        }
+
+        Some(element)
+    }
+}
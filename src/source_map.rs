@@ -0,0 +1,306 @@
+//! Contains a registry of named source texts that can resolve [`Span`]s to lines and columns.
+
+use core::fmt::{self, Display};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    span::Span,
+    str::{StrExt, lines::line_end},
+};
+
+/// Identifies a file registered in a [`SourceMap`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct FileId(usize);
+
+impl FileId {
+    /// Gets the index of the file within its [`SourceMap`].
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A [`Span`] qualified with the file that it points into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FileSpan {
+    pub file: FileId,
+    pub span: Span,
+}
+
+/// A column within a line measured in several units. All values are zero-based.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Column {
+    /// The number of bytes between the start of the line and the position.
+    pub bytes: usize,
+    /// The number of `char`s between the start of the line and the position.
+    pub chars: usize,
+    /// The number of UTF-16 code units between the start of the line and the position.
+    pub utf16: usize,
+}
+
+/// A resolved position within a [`SourceFile`]. Lines and columns are zero-based.
+///
+/// The [`Display`] implementation prints a one-based `line:column` pair where the column is
+/// measured in `char`s.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Position {
+    pub line: usize,
+    pub column: Column,
+}
+
+/// The resolved start and end of a [`Span`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SpanLocation {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A named source text with a precomputed index of line starts.
+#[derive(Clone, Debug)]
+pub struct SourceFile {
+    name: String,
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        let text = text.into();
+
+        let line_starts = core::iter::once(0)
+            .chain(
+                text.line_spans_inclusive()
+                    .map(|line| line.end)
+                    .filter(|end| text[..*end].ends_with(['\n', '\r'])),
+            )
+            .collect();
+
+        Self {
+            name: name.into(),
+            text,
+            line_starts,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The span of the whole file.
+    pub fn span(&self) -> Span {
+        Span::from(0..self.text.len())
+    }
+
+    /// The number of lines in the file. A trailing line terminator starts a new (empty) line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Gets the span of a line excluding its line terminator. Lines are split the same way as
+    /// [`StrExt::line_spans`].
+    /// # Example
+    /// ```rust
+    /// # use wutil::source_map::SourceFile;
+    /// # use wutil::Span;
+    /// let file = SourceFile::new("main.rs", "fn main() {\r\n}\n");
+    ///
+    /// assert_eq!(file.line_span(0), Some(Span::from(0..11)));
+    /// assert_eq!(file.line_span(1), Some(Span::from(13..14)));
+    /// assert_eq!(file.line_span(2), Some(Span::from(15..15)));
+    /// assert_eq!(file.line_span(3), None);
+    /// ```
+    pub fn line_span(&self, line: usize) -> Option<Span> {
+        let start = *self.line_starts.get(line)?;
+        let (end, _) = line_end(self.text.as_bytes(), start);
+
+        Some(Span::from(start..end))
+    }
+
+    /// Gets the zero-based line that contains `byte_index`. The end of the file belongs to the last line.
+    pub fn line_index(&self, byte_index: usize) -> Option<usize> {
+        if byte_index > self.text.len() {
+            return None;
+        }
+
+        Some(
+            self.line_starts
+                .partition_point(|start| *start <= byte_index)
+                - 1,
+        )
+    }
+
+    /// Resolves a byte index to a line and column. Returns `None` if the index is out of bounds
+    /// or is not on a `char` boundary.
+    /// # Example
+    /// ```rust
+    /// # use wutil::source_map::{SourceFile, Column};
+    /// let file = SourceFile::new("greeting.txt", "hi\nh€llo 𝄞!");
+    ///
+    /// let pos = file.position(15).unwrap();
+    ///
+    /// assert_eq!(pos.line, 1);
+    /// assert_eq!(pos.column, Column { bytes: 12, chars: 7, utf16: 8 });
+    /// assert_eq!(pos.to_string(), "2:8");
+    ///
+    /// assert_eq!(file.position(5), None);
+    /// ```
+    pub fn position(&self, byte_index: usize) -> Option<Position> {
+        let line = self.line_index(byte_index)?;
+        let line_start = self.line_starts[line];
+
+        let mut column = Column {
+            bytes: byte_index - line_start,
+            chars: 0,
+            utf16: 0,
+        };
+
+        let mut idx = line_start;
+        while idx < byte_index {
+            let char_span = self.text.char_span(idx)?;
+
+            column.chars += 1;
+            column.utf16 += if char_span.len() == 4 { 2 } else { 1 };
+            idx = char_span.end;
+        }
+
+        (idx == byte_index).then_some(Position { line, column })
+    }
+
+    /// Resolves both ends of a span.
+    pub fn resolve(&self, span: Span) -> Option<SpanLocation> {
+        Some(SpanLocation {
+            start: self.position(span.start)?,
+            end: self.position(span.end)?,
+        })
+    }
+}
+
+/// A collection of [`SourceFile`]s that hands out [`FileSpan`]s.
+/// # Example
+/// ```rust
+/// # use wutil::source_map::SourceMap;
+/// # use wutil::Span;
+/// let mut map = SourceMap::new();
+///
+/// let lib = map.add_file("lib.rs", "mod foo;\nmod bar;\n");
+/// let bar = map.file_span(lib, Span::from(13..16)).unwrap();
+///
+/// assert_eq!(map.slice(bar), Some("bar"));
+/// assert_eq!(map.resolve(bar).unwrap().start.to_string(), "2:5");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new file and returns its id.
+    pub fn add_file(&mut self, name: impl Into<String>, text: impl Into<String>) -> FileId {
+        self.files.push(SourceFile::new(name, text));
+
+        FileId(self.files.len() - 1)
+    }
+
+    pub fn file(&self, id: FileId) -> Option<&SourceFile> {
+        self.files.get(id.0)
+    }
+
+    /// Iterates over every registered file and its id.
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files.iter().enumerate().map(|(i, f)| (FileId(i), f))
+    }
+
+    /// Creates a [`FileSpan`]. Returns `None` if `span` does not fit within the file.
+    pub fn file_span(&self, file: FileId, span: Span) -> Option<FileSpan> {
+        let len = self.file(file)?.text.len();
+
+        (span.start <= span.end && span.end <= len).then_some(FileSpan { file, span })
+    }
+
+    /// Gets the text that a [`FileSpan`] points to.
+    pub fn slice(&self, span: FileSpan) -> Option<&str> {
+        self.file(span.file)?
+            .text
+            .get(span.span.start..span.span.end)
+    }
+
+    /// Resolves the start and end of a [`FileSpan`] to lines and columns.
+    pub fn resolve(&self, span: FileSpan) -> Option<SpanLocation> {
+        self.file(span.file)?.resolve(span.span)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column.chars + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_line_boundaries() {
+        let file = SourceFile::new("test", "ab\n\ncd");
+
+        let expected = [
+            (0, 0, 0),
+            (1, 0, 1),
+            (2, 0, 2),
+            (3, 1, 0),
+            (4, 2, 0),
+            (6, 2, 2),
+        ];
+
+        for (offset, line, col) in expected {
+            let pos = file.position(offset).unwrap();
+            assert_eq!((pos.line, pos.column.bytes), (line, col), "offset {offset}");
+        }
+
+        assert_eq!(file.position(7), None);
+        assert_eq!(file.line_count(), 3);
+    }
+
+    #[test]
+    fn line_spans_trim_terminators() {
+        let file = SourceFile::new("test", "a\r\nbc\rd\n\n");
+
+        let lines: Vec<_> = (0..file.line_count())
+            .map(|line| file.line_span(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                Span::from(0..1),
+                Span::from(3..5),
+                Span::from(6..7),
+                Span::from(8..8),
+                Span::from(9..9),
+            ]
+        );
+
+        for (line, span) in lines.into_iter().enumerate() {
+            assert_eq!(file.text().line_span_at(span.start), Some(span));
+            assert_eq!(file.text().line_number_at(span.start), Some(line));
+        }
+    }
+
+    #[test]
+    fn empty_file() {
+        let file = SourceFile::new("empty", "");
+
+        assert_eq!(file.line_count(), 1);
+        assert_eq!(file.line_span(0), Some(Span::from(0..0)));
+        assert_eq!(file.position(0), Some(Position::default()));
+    }
+}
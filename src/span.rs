@@ -1,59 +1,456 @@
 //! Contains a thin `Copy` wrapper around `Range<usize>`. This has the same layout as `Range<usize>`.
+//!
+//! [`GenericSpan`] is the same wrapper for other index types, such as `u32` for spans that never
+//! exceed 4 GiB.
+//!
+//! Spans implement the same traits as a `#[derive]`d struct with `start` and `end` fields would,
+//! and are ordered by their start and then their end.
+//! # Example
+//! ```rust
+//! # use wutil::{Span, span::GenericSpan};
+//! const FUNCTION: Span = Span::new(10, 40);
+//! const NAME: Span = FUNCTION.span_at().with_len(3);
+//!
+//! assert_eq!(NAME, Span::from(10..13));
+//! assert_eq!(NAME + 5, Span::from(15..18));
+//! assert_eq!(NAME - 10, Span::from(0..3));
+//!
+//! assert_eq!(NAME.checked_shift(-11), None);
+//! assert_eq!(NAME.saturating_shift(-11), Span::from(0..2));
+//!
+//! assert_eq!(Span::from(12..20).relative_to(FUNCTION), Some(Span::from(2..10)));
+//! assert_eq!(Span::from(35..45).relative_to(FUNCTION), None);
+//!
+//! // Span arithmetic on the built-in index types can build tables in `const` contexts
+//! const TABLE: [GenericSpan<u32>; 2] = {
+//!     let header = GenericSpan::<u32>::new(0, 3).with_len(2);
+//!     let body = match header.span_after().checked_shift(1) {
+//!         Some(at) => at.with_len(4),
+//!         None => panic!(),
+//!     };
+//!     [header, body]
+//! };
+//! const BODY_LEN: u32 = TABLE[1].len();
+//! const BODY_IN_HEADER: Option<GenericSpan<u32>> = TABLE[1].relative_to(TABLE[0]);
+//! const SHIFTED: Span = Span::new(0, 3).saturating_shift(-1);
+//! const EMPTY: bool = SHIFTED.is_empty();
+//!
+//! assert_eq!(TABLE, [GenericSpan::new(0, 2), GenericSpan::new(3, 7)]);
+//! assert_eq!((BODY_LEN, BODY_IN_HEADER), (4, None));
+//! assert_eq!((SHIFTED, EMPTY), (Span::new(0, 2), false));
+//! ```
 
 use core::{
     borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
     mem,
-    ops::{Deref, DerefMut, Index, IndexMut, Range},
+    ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, Range, Sub, SubAssign},
+    str::FromStr,
 };
 
 pub use crate::Span;
 
+mod checked;
+mod edit;
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "alloc")]
+mod set;
+mod spanned;
+pub use checked::{SnapMode, SpanError};
+pub use edit::{DeletionPolicy, SpanRebaser, Stickiness, TextEdit};
+#[cfg(feature = "alloc")]
+pub use map::{SpanMap, SpanMapIter};
+#[cfg(feature = "alloc")]
+pub use set::SpanSet;
+pub use spanned::{IgnoreSpan, Spanned};
+
 // So that it `Span` appears defined at root-level
 pub(crate) mod s {
-    use core::{
-        mem::{self, MaybeUninit},
-        ops::Range,
-    };
+    use core::mem::MaybeUninit;
 
-    #[repr(C)]
-    #[derive(Clone, Copy)]
-    struct SizeAndAlign<const SIZE: usize, Align> {
-        a: [u8; SIZE],
-        b: [Align; 0],
-    }
+    use super::SpanIndex;
 
     /// A thin `Copy` wrapper around `Range<usize>`. This has the same layout as `Range<usize>`.
+    pub type Span = GenericSpan<usize>;
+
+    /// A thin `Copy` wrapper around `Range<Idx>`. This has the same layout as `Range<Idx>`.
     #[repr(C)]
-    #[derive(Clone, Copy)]
-    pub struct Span {
-        inner: MaybeUninit<SizeAndAlign<{ mem::size_of::<Range<usize>>() }, usize>>,
+    pub struct GenericSpan<Idx> {
+        inner: [MaybeUninit<Idx>; 2],
     }
-}
 
-impl Span {
-    /// A zero-width span at the end of a span
-    pub fn span_after(self) -> Self {
-        (self.end..self.end).into()
+    impl<Idx: Copy> Clone for GenericSpan<Idx> {
+        fn clone(&self) -> Self {
+            *self
+        }
     }
 
-    /// A zero-width span at the start of a span
-    pub fn span_at(self) -> Self {
-        (self.start..self.start).into()
+    impl<Idx: Copy> Copy for GenericSpan<Idx> {}
+
+    impl<Idx: SpanIndex> GenericSpan<Idx> {
+        /// Creates a span from its start and end
+        /// ```rust
+        /// # use wutil::span::{GenericSpan, Span};
+        /// assert_eq!(Span::new(2, 5), Span::from(2..5));
+        ///
+        /// let small: GenericSpan<u32> = GenericSpan::new(2, 5);
+        /// assert_eq!(small.start, 2u32);
+        ///
+        /// const KEYWORDS: [Span; 2] = [Span::new(0, 3), Span::new(8, 14)];
+        /// assert_eq!(&"let x = return;"[KEYWORDS[1]], "return");
+        /// ```
+        pub const fn new(start: Idx, end: Idx) -> Self {
+            Self {
+                inner: [MaybeUninit::new(start), MaybeUninit::new(end)],
+            }
+        }
+
+        /// Gets the start of a span. This is the same as `span.start`, but can be used in `const`
+        /// contexts.
+        pub const fn start(self) -> Idx {
+            unsafe { self.inner[0].assume_init() }
+        }
+
+        /// Gets the end of a span. This is the same as `span.end`, but can be used in `const`
+        /// contexts.
+        pub const fn end(self) -> Idx {
+            unsafe { self.inner[1].assume_init() }
+        }
     }
+}
+
+pub use s::GenericSpan;
+
+/// An integer-like type that can be used as the bounds of a [`GenericSpan`].
+/// # Example
+/// ```rust
+/// # use wutil::span::{GenericSpan, SpanArithmetic, SpanIndex};
+/// #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// struct ByteOffset(u32);
+///
+/// impl SpanIndex for ByteOffset {
+///     const MIN: Self = ByteOffset(u32::MIN);
+///     const MAX: Self = ByteOffset(u32::MAX);
+///
+///     fn from_usize(value: usize) -> Option<Self> {
+///         u32::from_usize(value).map(ByteOffset)
+///     }
+///
+///     fn to_usize(self) -> Option<usize> {
+///         self.0.to_usize()
+///     }
+///
+///     fn checked_add(self, rhs: Self) -> Option<Self> {
+///         self.0.checked_add(rhs.0).map(ByteOffset)
+///     }
+///
+///     fn checked_sub(self, rhs: Self) -> Option<Self> {
+///         self.0.checked_sub(rhs.0).map(ByteOffset)
+///     }
+/// }
+///
+/// let span = GenericSpan::new(ByteOffset(4), ByteOffset(9));
+///
+/// assert_eq!(&"Hello, world!"[span], "o, wo");
+/// assert_eq!(&"Hello, world!"[span + ByteOffset(2)], " worl");
+/// assert!(span.len() == ByteOffset(5));
+/// assert_eq!(core::mem::size_of_val(&span), 8);
+/// ```
+pub trait SpanIndex: Copy + Ord {
+    /// The smallest index. This is the length of spans that end before they start.
+    const MIN: Self;
+    /// The largest index.
+    const MAX: Self;
+
+    /// Converts a `usize` to this type. Returns `None` if the value does not fit.
+    fn from_usize(value: usize) -> Option<Self>;
+    /// Converts this type to a `usize`. Returns `None` if the value does not fit.
+    fn to_usize(self) -> Option<usize>;
+    /// Adds two indices. Returns `None` if the result does not fit.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Subtracts two indices. Returns `None` if the result does not fit.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_span_index {
+    ($($t:ty => $signed:ty),*) => {$(
+        impl SpanIndex for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn from_usize(value: usize) -> Option<Self> {
+                value.try_into().ok()
+            }
+
+            fn to_usize(self) -> Option<usize> {
+                self.try_into().ok()
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+
+        /// `const` versions of the [`SpanArithmetic`] methods. Shifts take a signed delta of the
+        /// same width as the index.
+        impl GenericSpan<$t> {
+            /// Sets the length of a span without changing its start
+            /// # Panics
+            /// Panics if the end would overflow.
+            pub const fn with_len(self, len: $t) -> Self {
+                match self.start().checked_add(len) {
+                    Some(end) => Self::new(self.start(), end),
+                    None => panic!("span end overflowed"),
+                }
+            }
+
+            /// The length of a span. This is zero if the span's end is before its start.
+            pub const fn len(self) -> $t {
+                self.end().saturating_sub(self.start())
+            }
+
+            /// Checks if a span contains no items.
+            pub const fn is_empty(self) -> bool {
+                self.start() >= self.end()
+            }
+
+            /// Moves a span by `delta`. Returns `None` if either bound would overflow.
+            pub const fn checked_shift(self, delta: $signed) -> Option<Self> {
+                match (
+                    self.start().checked_add_signed(delta),
+                    self.end().checked_add_signed(delta),
+                ) {
+                    (Some(start), Some(end)) => Some(Self::new(start, end)),
+                    _ => None,
+                }
+            }
+
+            /// Moves a span by `delta`. Each bound saturates at the numeric bounds of the index
+            /// type, so the span may shrink.
+            pub const fn saturating_shift(self, delta: $signed) -> Self {
+                Self::new(
+                    self.start().saturating_add_signed(delta),
+                    self.end().saturating_add_signed(delta),
+                )
+            }
+
+            /// Converts an absolute span into a span relative to the start of `base`. Returns
+            /// `None` if `base` does not contain this span.
+            pub const fn relative_to(self, base: Self) -> Option<Self> {
+                if base.start() <= self.start() && self.end() <= base.end() {
+                    Some(Self::new(
+                        self.start() - base.start(),
+                        self.end() - base.start(),
+                    ))
+                } else {
+                    None
+                }
+            }
+        }
+    )*};
+}
+
+impl_span_index!(u8 => i8, u16 => i16, u32 => i32, u64 => i64, usize => isize);
+
+/// Span arithmetic for any [`SpanIndex`], including custom index types.
+///
+/// The built-in index types have `const` inherent methods with the same names, which are used
+/// instead of these whenever the index type is known.
+/// # Example
+/// ```rust
+/// # use wutil::span::{GenericSpan, SpanArithmetic, SpanIndex};
+/// /// Gets where `span` starts within `base` and its length.
+/// fn locate<Idx: SpanIndex>(span: GenericSpan<Idx>, base: GenericSpan<Idx>) -> Option<(Idx, Idx)> {
+///     let relative = span.relative_to(base)?;
+///     Some((relative.start, relative.len()))
+/// }
+///
+/// let base = GenericSpan::<u32>::new(4, 10);
+/// assert_eq!(locate(GenericSpan::new(6, 8), base), Some((2, 2)));
+/// assert_eq!(locate(GenericSpan::<u32>::new(6, 8).saturating_shift(3), base), None);
+/// ```
+pub trait SpanArithmetic: Sized {
+    /// The type of the bounds of the span.
+    type Index;
 
     /// Sets the length of a span without changing its start
-    pub fn with_len(self, len: usize) -> Self {
-        (self.start..self.start + len).into()
+    /// # Panics
+    /// Panics if the end would overflow.
+    fn with_len(self, len: Self::Index) -> Self;
+    /// The length of a span. This is zero if the span's end is before its start.
+    fn len(self) -> Self::Index;
+    /// Checks if a span contains no items.
+    fn is_empty(&self) -> bool;
+    /// Moves a span by `delta`. Returns `None` if either bound would overflow.
+    fn checked_shift(self, delta: isize) -> Option<Self>;
+    /// Moves a span by `delta`. Each bound saturates at the numeric bounds of the index type, so
+    /// the span may shrink.
+    fn saturating_shift(self, delta: isize) -> Self;
+    /// Converts an absolute span into a span relative to the start of `base`. Returns `None` if
+    /// `base` does not contain this span.
+    fn relative_to(self, base: Self) -> Option<Self>;
+}
+
+impl<Idx: SpanIndex> SpanArithmetic for GenericSpan<Idx> {
+    type Index = Idx;
+
+    fn with_len(self, len: Idx) -> Self {
+        let end = self.start.checked_add(len).expect("span end overflowed");
+        Self::new(self.start, end)
+    }
+
+    fn len(self) -> Idx {
+        self.end.checked_sub(self.start).unwrap_or(Idx::MIN)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    fn checked_shift(self, delta: isize) -> Option<Self> {
+        Some(Self::new(
+            shift_index(self.start, delta)?,
+            shift_index(self.end, delta)?,
+        ))
+    }
+
+    fn saturating_shift(self, delta: isize) -> Self {
+        let limit = if delta < 0 { Idx::MIN } else { Idx::MAX };
+
+        Self::new(
+            shift_index(self.start, delta).unwrap_or(limit),
+            shift_index(self.end, delta).unwrap_or(limit),
+        )
+    }
+
+    fn relative_to(self, base: Self) -> Option<Self> {
+        if !base.contains_span(self) {
+            return None;
+        }
+
+        Some(Self::new(
+            self.start.checked_sub(base.start)?,
+            self.end.checked_sub(base.start)?,
+        ))
+    }
+}
+
+/// Moves a bound by `delta`. Returns `None` if it would overflow.
+fn shift_index<Idx: SpanIndex>(index: Idx, delta: isize) -> Option<Idx> {
+    let magnitude = Idx::from_usize(delta.unsigned_abs())?;
+
+    if delta < 0 {
+        index.checked_sub(magnitude)
+    } else {
+        index.checked_add(magnitude)
+    }
+}
+
+/// Shifts a span forward.
+/// # Panics
+/// Panics if either bound overflows.
+impl<Idx: SpanIndex> Add<Idx> for GenericSpan<Idx> {
+    type Output = Self;
+
+    fn add(self, rhs: Idx) -> Self {
+        let shift = |index: Idx| index.checked_add(rhs).expect("span bound overflowed");
+        Self::new(shift(self.start), shift(self.end))
+    }
+}
+
+/// Shifts a span backward.
+/// # Panics
+/// Panics if either bound underflows.
+impl<Idx: SpanIndex> Sub<Idx> for GenericSpan<Idx> {
+    type Output = Self;
+
+    fn sub(self, rhs: Idx) -> Self {
+        let shift = |index: Idx| index.checked_sub(rhs).expect("span bound underflowed");
+        Self::new(shift(self.start), shift(self.end))
+    }
+}
+
+impl<Idx: SpanIndex> AddAssign<Idx> for GenericSpan<Idx> {
+    fn add_assign(&mut self, rhs: Idx) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Idx: SpanIndex> SubAssign<Idx> for GenericSpan<Idx> {
+    fn sub_assign(&mut self, rhs: Idx) {
+        *self = *self - rhs;
+    }
+}
+
+macro_rules! impl_span_widening {
+    ($($from:ty => $($to:ty),*;)*) => {$($(
+        impl From<GenericSpan<$from>> for GenericSpan<$to> {
+            fn from(value: GenericSpan<$from>) -> Self {
+                (<$to>::from(value.start)..<$to>::from(value.end)).into()
+            }
+        }
+    )*)*};
+}
+
+impl_span_widening! {
+    u8 => u16, u32, u64, usize;
+    u16 => u32, u64, usize;
+    u32 => u64;
+}
+
+impl<Idx: SpanIndex> GenericSpan<Idx> {
+    /// Converts a span to a different index type. Returns `None` if either bound does not fit.
+    /// ```rust
+    /// # use wutil::span::{GenericSpan, Span};
+    /// let span = Span::from(10..300);
+    ///
+    /// assert_eq!(span.try_cast::<u16>(), Some(GenericSpan::<u16>::new(10, 300)));
+    /// assert_eq!(span.try_cast::<u8>(), None);
+    ///
+    /// let wide: GenericSpan<u64> = GenericSpan::<u16>::new(10, 300).into();
+    /// assert_eq!(wide.try_cast::<usize>(), Some(span));
+    /// ```
+    pub fn try_cast<T: SpanIndex>(self) -> Option<GenericSpan<T>> {
+        let start = T::from_usize(self.start.to_usize()?)?;
+        let end = T::from_usize(self.end.to_usize()?)?;
+
+        Some((start..end).into())
+    }
+
+    /// Converts a span to a `Range<usize>` for indexing. Panics if the span does not fit.
+    fn to_usize_range(self) -> Range<usize> {
+        self.try_cast::<usize>()
+            .expect("span does not fit in a usize")
+            .into()
+    }
+
+    /// A zero-width span at the end of a span
+    pub const fn span_after(self) -> Self {
+        Self::at(self.end())
+    }
+
+    /// A zero-width span at the start of a span
+    pub const fn span_at(self) -> Self {
+        Self::at(self.start())
     }
 
     /// Sets the start of a span without changing its end
-    pub fn with_start(self, start: usize) -> Self {
-        (start..self.end).into()
+    pub const fn with_start(self, start: Idx) -> Self {
+        Self::new(start, self.end())
     }
 
     /// Sets the end of a span without changing its start
-    pub fn with_end(self, end: usize) -> Self {
-        (self.start..end).into()
+    pub const fn with_end(self, end: Idx) -> Self {
+        Self::new(self.start(), end)
     }
 
     /// Returns the zero-width span at a certain position
@@ -63,11 +460,12 @@ impl Span {
     /// assert_eq!(Span::at(0), Span::from(0..0));
     /// assert_eq!(Span::at(50), Span::from(50..50));
     /// ```
-    pub fn at(pos: usize) -> Self {
-        (pos..pos).into()
+    pub const fn at(pos: Idx) -> Self {
+        Self::new(pos, pos)
     }
 
-    /// Checks if this `Span` overlaps with another `Span`.
+    /// Checks if this `Span` overlaps with another `Span`, meaning that they share at least one
+    /// position. Empty spans have no positions, so they never overlap with anything.
     /// ```rust
     /// # use wutil::span::Span;
     /// let foo = Span::from(0..3);
@@ -78,10 +476,138 @@ impl Span {
     /// assert!(foo.overlaps_with(bar));
     /// assert!(bar.overlaps_with(biz));
     ///
-    /// assert!(foo.overlaps_with(Span::from(0..0)));
+    /// assert!(!foo.overlaps_with(Span::from(0..0)));
+    /// assert!(!foo.overlaps_with(Span::at(1)));
     /// ```
     pub fn overlaps_with(self, other: Self) -> bool {
-        self.contains(&other.start) || other.contains(&self.start)
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
+    }
+
+    /// Gets the span shared by two spans. This returns `Some` exactly when [`Span::overlaps_with`]
+    /// returns `true`, so the intersection is never empty.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// let foo = Span::from(0..5);
+    ///
+    /// assert_eq!(foo.intersection(Span::from(3..8)), Some(Span::from(3..5)));
+    /// assert_eq!(foo.intersection(Span::from(1..2)), Some(Span::from(1..2)));
+    /// assert_eq!(foo.intersection(Span::from(5..8)), None);
+    ///
+    /// assert_eq!(foo.intersection(Span::at(2)), None);
+    /// ```
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        if !self.overlaps_with(other) {
+            return None;
+        }
+
+        Some((self.start.max(other.start)..self.end.min(other.end)).into())
+    }
+
+    /// Gets the smallest span that contains both spans.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// assert_eq!(Span::from(2..4).cover(Span::from(7..9)), Span::from(2..9));
+    /// assert_eq!(Span::from(2..9).cover(Span::from(4..5)), Span::from(2..9));
+    /// assert_eq!(Span::at(3).cover(Span::at(1)), Span::from(1..3));
+    /// ```
+    pub fn cover(self, other: Self) -> Self {
+        (self.start.min(other.start)..self.end.max(other.end)).into()
+    }
+
+    /// Gets the span containing the positions of both spans. Returns `None` if there would be a gap
+    /// between them. Empty spans have no positions, so the union with an empty span is the other span.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// let foo = Span::from(2..5);
+    ///
+    /// assert_eq!(foo.union(Span::from(4..8)), Some(Span::from(2..8)));
+    /// assert_eq!(foo.union(Span::from(5..8)), Some(Span::from(2..8)));
+    /// assert_eq!(foo.union(Span::from(6..8)), None);
+    ///
+    /// assert_eq!(foo.union(Span::at(10)), Some(foo));
+    /// ```
+    pub fn union(self, other: Self) -> Option<Self> {
+        if other.is_empty() {
+            return Some(self);
+        }
+
+        if self.is_empty() {
+            return Some(other);
+        }
+
+        (self.overlaps_with(other) || self.is_adjacent_to(other)).then(|| self.cover(other))
+    }
+
+    /// Removes the positions of `other` from this span. The first piece is the part of `self`
+    /// before `other`, and the second piece is the part of `self` after `other`.
+    ///
+    /// Pieces are never empty, so an empty span has no pieces and removing an empty span
+    /// leaves `self` whole.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// let foo = Span::from(2..8);
+    ///
+    /// assert_eq!(
+    ///     foo.difference(Span::from(4..5)),
+    ///     (Some(Span::from(2..4)), Some(Span::from(5..8)))
+    /// );
+    /// assert_eq!(foo.difference(Span::from(0..4)), (None, Some(Span::from(4..8))));
+    /// assert_eq!(foo.difference(Span::from(6..9)), (Some(Span::from(2..6)), None));
+    /// assert_eq!(foo.difference(Span::from(0..9)), (None, None));
+    ///
+    /// assert_eq!(foo.difference(Span::from(8..9)), (Some(foo), None));
+    /// assert_eq!(foo.difference(Span::at(5)), (Some(foo), None));
+    /// assert_eq!(Span::at(5).difference(Span::from(0..2)), (None, None));
+    /// ```
+    pub fn difference(self, other: Self) -> (Option<Self>, Option<Self>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+
+        if other.is_empty() || !self.overlaps_with(other) {
+            return (Some(self), None);
+        }
+
+        let before = self.start..other.start;
+        let after = other.end..self.end;
+
+        (
+            (!before.is_empty()).then(|| before.into()),
+            (!after.is_empty()).then(|| after.into()),
+        )
+    }
+
+    /// Checks if every position of `other` is within this span. An empty span is contained by
+    /// any span that it is inside of or at the edge of.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// let foo = Span::from(2..6);
+    ///
+    /// assert!(foo.contains_span(Span::from(3..5)));
+    /// assert!(foo.contains_span(foo));
+    /// assert!(!foo.contains_span(Span::from(5..7)));
+    ///
+    /// assert!(foo.contains_span(Span::at(6)));
+    /// assert!(!foo.contains_span(Span::at(7)));
+    /// ```
+    pub fn contains_span(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Checks if one of the spans ends exactly where the other one starts.
+    /// ```rust
+    /// # use wutil::span::Span;
+    /// let foo = Span::from(2..6);
+    ///
+    /// assert!(foo.is_adjacent_to(Span::from(6..9)));
+    /// assert!(foo.is_adjacent_to(Span::from(0..2)));
+    /// assert!(!foo.is_adjacent_to(Span::from(7..9)));
+    /// assert!(!foo.is_adjacent_to(Span::from(5..9)));
+    ///
+    /// assert!(foo.is_adjacent_to(Span::at(6)));
+    /// ```
+    pub fn is_adjacent_to(self, other: Self) -> bool {
+        self.end == other.start || other.end == self.start
     }
 }
 
@@ -98,116 +624,226 @@ impl Iterator for SpanIterator {
     }
 }
 
-#[cfg(feature = "alloc")]
-mod debug {
-    use core::{fmt::Debug, ops::Deref};
+/// An error returned when parsing a span with [`str::parse`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseSpanError<E> {
+    /// The text does not contain `..`.
+    MissingSeparator,
+    /// The start of the span could not be parsed.
+    Start(E),
+    /// The end of the span could not be parsed.
+    End(E),
+}
 
-    use super::Span;
+impl<E: Display> Display for ParseSpanError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSeparator => f.write_str("missing `..` in span"),
+            Self::Start(err) => write!(f, "invalid span start: {err}"),
+            Self::End(err) => write!(f, "invalid span end: {err}"),
+        }
+    }
+}
 
-    impl Debug for Span {
-        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-            Debug::fmt(self.deref(), f)
+impl<E: core::error::Error + 'static> core::error::Error for ParseSpanError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::MissingSeparator => None,
+            Self::Start(err) | Self::End(err) => Some(err),
         }
     }
 }
 
+impl<Idx: SpanIndex> GenericSpan<Idx> {
+    /// Fails to compile if `GenericSpan<Idx>` cannot be transmuted to and from `Range<Idx>`.
+    ///
+    /// `Range` is `repr(Rust)`, so its fields must also be checked to be in the same order as
+    /// `inner`, which the `const` accessors read from.
+    const SAME_LAYOUT: () = assert!(
+        mem::size_of::<Self>() == mem::size_of::<Range<Idx>>()
+            && mem::align_of::<Self>() == mem::align_of::<Range<Idx>>()
+            && mem::offset_of!(Range<Idx>, start) == 0
+            && mem::offset_of!(Range<Idx>, end) == mem::size_of::<Idx>()
+    );
+}
+
 // Boilerplate //
 
-impl PartialEq for Span {
+impl<Idx: SpanIndex> PartialEq for GenericSpan<Idx> {
     fn eq(&self, other: &Self) -> bool {
         self.deref().eq(other)
     }
 }
 
-impl Eq for Span {}
+impl<Idx: SpanIndex> Eq for GenericSpan<Idx> {}
+
+/// Spans are ordered by their start, and then by their end.
+/// ```rust
+/// # use wutil::Span;
+/// let mut spans = [Span::from(4..6), Span::from(2..9), Span::from(2..3)];
+/// spans.sort();
+///
+/// assert_eq!(spans, [Span::from(2..3), Span::from(2..9), Span::from(4..6)]);
+/// ```
+impl<Idx: SpanIndex> PartialOrd for GenericSpan<Idx> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-impl From<Range<usize>> for Span {
-    fn from(value: Range<usize>) -> Self {
-        unsafe { mem::transmute::<Range<usize>, Span>(value) }
+impl<Idx: SpanIndex> Ord for GenericSpan<Idx> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.start, self.end).cmp(&(other.start, other.end))
     }
 }
 
-impl From<Span> for Range<usize> {
-    fn from(value: Span) -> Self {
-        unsafe { mem::transmute::<Span, Range<usize>>(value) }
+impl<Idx: SpanIndex + Hash> Hash for GenericSpan<Idx> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state);
     }
 }
 
-impl AsRef<Range<usize>> for Span {
-    fn as_ref(&self) -> &Range<usize> {
-        unsafe { crate::transmute_ref::<Span, Range<usize>>(self) }
+/// An empty span at the default index (usually zero).
+impl<Idx: SpanIndex + Default> Default for GenericSpan<Idx> {
+    fn default() -> Self {
+        Self::at(Idx::default())
     }
 }
 
-impl AsRef<Span> for Range<usize> {
-    fn as_ref(&self) -> &Span {
-        unsafe { crate::transmute_ref::<Range<usize>, Span>(self) }
+impl<Idx: SpanIndex + Debug> Debug for GenericSpan<Idx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.deref(), f)
     }
 }
 
-impl AsMut<Range<usize>> for Span {
-    fn as_mut(&mut self) -> &mut Range<usize> {
-        unsafe { crate::transmute_mut::<Span, Range<usize>>(self) }
+/// Formats a span with range syntax.
+/// ```rust
+/// # use wutil::Span;
+/// assert_eq!(Span::from(5..9).to_string(), "5..9");
+/// ```
+impl<Idx: SpanIndex + Display> Display for GenericSpan<Idx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.start, f)?;
+        f.write_str("..")?;
+        Display::fmt(&self.end, f)
     }
 }
 
-impl AsMut<Span> for Range<usize> {
-    fn as_mut(&mut self) -> &mut Span {
-        unsafe { crate::transmute_mut::<Range<usize>, Span>(self) }
+/// Parses a span with range syntax.
+/// ```rust
+/// # use wutil::span::{Span, ParseSpanError};
+/// assert_eq!("5..9".parse(), Ok(Span::from(5..9)));
+/// assert_eq!("5".parse::<Span>(), Err(ParseSpanError::MissingSeparator));
+/// assert!(matches!("5..x".parse::<Span>(), Err(ParseSpanError::End(_))));
+/// ```
+impl<Idx: SpanIndex + FromStr> FromStr for GenericSpan<Idx> {
+    type Err = ParseSpanError<Idx::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once("..").ok_or(ParseSpanError::MissingSeparator)?;
+
+        let start = start.parse().map_err(ParseSpanError::Start)?;
+        let end = end.parse().map_err(ParseSpanError::End)?;
+
+        Ok(Self::new(start, end))
+    }
+}
+
+impl<Idx: SpanIndex> From<Range<Idx>> for GenericSpan<Idx> {
+    fn from(value: Range<Idx>) -> Self {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl<Idx: SpanIndex> From<GenericSpan<Idx>> for Range<Idx> {
+    fn from(value: GenericSpan<Idx>) -> Self {
+        value.start()..value.end()
+    }
+}
+
+impl<Idx: SpanIndex> AsRef<Range<Idx>> for GenericSpan<Idx> {
+    fn as_ref(&self) -> &Range<Idx> {
+        let () = Self::SAME_LAYOUT;
+
+        unsafe { crate::transmute_ref::<GenericSpan<Idx>, Range<Idx>>(self) }
+    }
+}
+
+impl<Idx: SpanIndex> AsRef<GenericSpan<Idx>> for Range<Idx> {
+    fn as_ref(&self) -> &GenericSpan<Idx> {
+        let () = GenericSpan::<Idx>::SAME_LAYOUT;
+
+        unsafe { crate::transmute_ref::<Range<Idx>, GenericSpan<Idx>>(self) }
+    }
+}
+
+impl<Idx: SpanIndex> AsMut<Range<Idx>> for GenericSpan<Idx> {
+    fn as_mut(&mut self) -> &mut Range<Idx> {
+        let () = Self::SAME_LAYOUT;
+
+        unsafe { crate::transmute_mut::<GenericSpan<Idx>, Range<Idx>>(self) }
+    }
+}
+
+impl<Idx: SpanIndex> AsMut<GenericSpan<Idx>> for Range<Idx> {
+    fn as_mut(&mut self) -> &mut GenericSpan<Idx> {
+        let () = GenericSpan::<Idx>::SAME_LAYOUT;
+
+        unsafe { crate::transmute_mut::<Range<Idx>, GenericSpan<Idx>>(self) }
     }
 }
 
-impl Deref for Span {
-    type Target = Range<usize>;
+impl<Idx: SpanIndex> Deref for GenericSpan<Idx> {
+    type Target = Range<Idx>;
 
     fn deref(&self) -> &Self::Target {
         self.as_ref()
     }
 }
 
-impl DerefMut for Span {
+impl<Idx: SpanIndex> DerefMut for GenericSpan<Idx> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut()
     }
 }
 
-impl Borrow<Range<usize>> for Span {
-    fn borrow(&self) -> &Range<usize> {
+impl<Idx: SpanIndex> Borrow<Range<Idx>> for GenericSpan<Idx> {
+    fn borrow(&self) -> &Range<Idx> {
         self.as_ref()
     }
 }
 
-impl BorrowMut<Range<usize>> for Span {
-    fn borrow_mut(&mut self) -> &mut Range<usize> {
+impl<Idx: SpanIndex> BorrowMut<Range<Idx>> for GenericSpan<Idx> {
+    fn borrow_mut(&mut self) -> &mut Range<Idx> {
         self.as_mut()
     }
 }
 
-impl<T> Index<Span> for [T] {
+impl<T, Idx: SpanIndex> Index<GenericSpan<Idx>> for [T] {
     type Output = [T];
 
-    fn index(&self, index: Span) -> &Self::Output {
-        &self[index.deref().clone()]
+    fn index(&self, index: GenericSpan<Idx>) -> &Self::Output {
+        &self[index.to_usize_range()]
     }
 }
 
-impl<T> IndexMut<Span> for [T] {
-    fn index_mut(&mut self, index: Span) -> &mut Self::Output {
-        &mut self[index.deref().clone()]
+impl<T, Idx: SpanIndex> IndexMut<GenericSpan<Idx>> for [T] {
+    fn index_mut(&mut self, index: GenericSpan<Idx>) -> &mut Self::Output {
+        &mut self[index.to_usize_range()]
     }
 }
 
-impl Index<Span> for str {
+impl<Idx: SpanIndex> Index<GenericSpan<Idx>> for str {
     type Output = str;
 
-    fn index(&self, index: Span) -> &Self::Output {
-        &self[index.deref().clone()]
+    fn index(&self, index: GenericSpan<Idx>) -> &Self::Output {
+        &self[index.to_usize_range()]
     }
 }
 
-impl IndexMut<Span> for str {
-    fn index_mut(&mut self, index: Span) -> &mut Self::Output {
-        &mut self[index.deref().clone()]
+impl<Idx: SpanIndex> IndexMut<GenericSpan<Idx>> for str {
+    fn index_mut(&mut self, index: GenericSpan<Idx>) -> &mut Self::Output {
+        &mut self[index.to_usize_range()]
     }
 }
 
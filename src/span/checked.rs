@@ -0,0 +1,189 @@
+use core::fmt::{self, Display};
+
+use super::{GenericSpan, Span, SpanIndex};
+use crate::str::StrExt;
+
+/// An error returned when a span cannot be used to slice a string or slice.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpanError {
+    /// The span ends after the end of the string or slice, which has length `len`.
+    OutOfBounds { len: usize },
+    /// The span's end is before its start.
+    Inverted,
+    /// The byte at `index` is in the middle of a UTF-8 sequence.
+    NotCharBoundary { index: usize },
+}
+
+impl Display for SpanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { len } => write!(f, "span is out of bounds of length {len}"),
+            Self::Inverted => f.write_str("span ends before it starts"),
+            Self::NotCharBoundary { index } => {
+                write!(f, "byte index {index} is not a char boundary")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SpanError {}
+
+/// Decides how [`Span::snap_to_char_boundaries`] moves bounds that split a character.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SnapMode {
+    /// Split characters become part of the span.
+    #[default]
+    Widen,
+    /// Split characters are removed from the span.
+    Narrow,
+}
+
+impl<Idx: SpanIndex> GenericSpan<Idx> {
+    /// Gets the text in a span without panicking.
+    /// ```rust
+    /// # use wutil::span::{Span, SpanError};
+    /// let text = "héllo";
+    ///
+    /// assert_eq!(Span::from(3..6).get_str(text), Ok("llo"));
+    /// assert_eq!(Span::from(3..7).get_str(text), Err(SpanError::OutOfBounds { len: 6 }));
+    /// assert_eq!(Span::from(4..3).get_str(text), Err(SpanError::Inverted));
+    /// assert_eq!(Span::from(0..2).get_str(text), Err(SpanError::NotCharBoundary { index: 2 }));
+    /// ```
+    pub fn get_str(self, s: &str) -> Result<&str, SpanError> {
+        let range = self.check_str(s)?;
+        Ok(&s[range])
+    }
+
+    /// Gets the text in a span mutably without panicking.
+    pub fn get_str_mut(self, s: &mut str) -> Result<&mut str, SpanError> {
+        let range = self.check_str(s)?;
+        Ok(&mut s[range])
+    }
+
+    /// Gets the items in a span without panicking.
+    /// ```rust
+    /// # use wutil::span::{Span, SpanError};
+    /// let items = [1, 2, 3];
+    ///
+    /// assert_eq!(Span::from(1..3).get_slice(&items), Ok(&items[1..]));
+    /// assert_eq!(Span::from(1..4).get_slice(&items), Err(SpanError::OutOfBounds { len: 3 }));
+    /// ```
+    pub fn get_slice<T>(self, slice: &[T]) -> Result<&[T], SpanError> {
+        let range = self.check_bounds(slice.len())?;
+        Ok(&slice[range])
+    }
+
+    /// Gets the items in a span mutably without panicking.
+    pub fn get_slice_mut<T>(self, slice: &mut [T]) -> Result<&mut [T], SpanError> {
+        let range = self.check_bounds(slice.len())?;
+        Ok(&mut slice[range])
+    }
+
+    fn check_bounds(self, len: usize) -> Result<core::ops::Range<usize>, SpanError> {
+        let span = self
+            .try_cast::<usize>()
+            .ok_or(SpanError::OutOfBounds { len })?;
+
+        if span.start > span.end {
+            return Err(SpanError::Inverted);
+        }
+
+        if span.end > len {
+            return Err(SpanError::OutOfBounds { len });
+        }
+
+        Ok(span.into())
+    }
+
+    fn check_str(self, s: &str) -> Result<core::ops::Range<usize>, SpanError> {
+        let range = self.check_bounds(s.len())?;
+
+        for index in [range.start, range.end] {
+            if !s.is_char_boundary(index) {
+                return Err(SpanError::NotCharBoundary { index });
+            }
+        }
+
+        Ok(range)
+    }
+}
+
+impl Span {
+    /// Moves the bounds of a span so that they do not split any characters of `s`. Bounds past
+    /// the end of `s` are moved to its end.
+    ///
+    /// When narrowing a span inside of a single character, the result is an empty span after that
+    /// character.
+    /// ```rust
+    /// # use wutil::span::{Span, SnapMode};
+    /// let text = "a→b";
+    ///
+    /// assert_eq!(Span::from(2..4).snap_to_char_boundaries(text, SnapMode::Widen), Span::from(1..4));
+    /// assert_eq!(Span::from(2..5).snap_to_char_boundaries(text, SnapMode::Narrow), Span::from(4..5));
+    /// assert_eq!(Span::from(2..3).snap_to_char_boundaries(text, SnapMode::Narrow), Span::at(4));
+    /// assert_eq!(Span::from(0..9).snap_to_char_boundaries(text, SnapMode::Widen), Span::from(0..5));
+    /// ```
+    pub fn snap_to_char_boundaries(self, s: &str, mode: SnapMode) -> Span {
+        let start = self.start.min(s.len());
+        let end = self.end.min(s.len());
+
+        let (start, end) = match mode {
+            SnapMode::Widen => (
+                containing_char(s, start).map_or(start, |c| c.start),
+                containing_char(s, end).map_or(end, |c| c.end),
+            ),
+            SnapMode::Narrow => (
+                containing_char(s, start).map_or(start, |c| c.end),
+                containing_char(s, end).map_or(end, |c| c.start),
+            ),
+        };
+
+        Span::from(start..end.max(start))
+    }
+}
+
+/// Gets the span of the character that `index` splits. Returns `None` if `index` is a char
+/// boundary.
+fn containing_char(s: &str, index: usize) -> Option<Span> {
+    if s.is_char_boundary(index) {
+        return None;
+    }
+
+    (index.saturating_sub(3)..index)
+        .rev()
+        .find_map(|pos| s.char_span(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapping() {
+        let text = "a→b€😀";
+
+        for start in 0..=text.len() + 1 {
+            for end in start..=text.len() + 1 {
+                let span = Span::from(start..end);
+                let widened = span.snap_to_char_boundaries(text, SnapMode::Widen);
+                let narrowed = span.snap_to_char_boundaries(text, SnapMode::Narrow);
+
+                assert!(widened.get_str(text).is_ok(), "{span:?}");
+                assert!(narrowed.get_str(text).is_ok(), "{span:?}");
+
+                if end <= text.len() {
+                    assert!(widened.contains_span(span), "{span:?}");
+                }
+
+                if !narrowed.is_empty() {
+                    assert!(span.contains_span(narrowed), "{span:?}");
+                }
+
+                if span.get_str(text).is_ok() {
+                    assert_eq!(widened, span);
+                    assert_eq!(narrowed, span);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::Span;
+
+/// A text edit that replaces the contents of `span` with `new_len` bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_len: usize,
+}
+
+impl TextEdit {
+    pub fn new(span: Span, new_len: usize) -> Self {
+        Self { span, new_len }
+    }
+
+    /// An edit that inserts `len` bytes at `pos`.
+    pub fn insert(pos: usize, len: usize) -> Self {
+        Self::new(Span::at(pos), len)
+    }
+
+    /// An edit that removes the contents of `span`.
+    pub fn delete(span: Span) -> Self {
+        Self::new(span, 0)
+    }
+
+    /// The span of the new text after the edit is applied.
+    pub fn new_span(self) -> Span {
+        self.span.with_len(self.new_len)
+    }
+
+    /// Moves a position through the edit. Positions within the replaced text are moved to the
+    /// start or end of the new text depending on `after`.
+    fn map(self, pos: usize, after: bool) -> usize {
+        let edit = self.span;
+
+        if pos < edit.start {
+            pos
+        } else if pos > edit.end {
+            pos - edit.len() + self.new_len
+        } else if pos == edit.start && (!edit.is_empty() || !after) {
+            pos
+        } else if pos == edit.end || after {
+            edit.start + self.new_len
+        } else {
+            edit.start
+        }
+    }
+}
+
+/// Decides if text inserted at the edge of a span becomes part of it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Stickiness {
+    /// Text inserted at either edge of a span is not part of it. Spans that partially overlap
+    /// with a replacement shrink so that they do not contain the new text.
+    #[default]
+    Exclude,
+    /// Text inserted at either edge of a span becomes part of it. Spans that partially overlap
+    /// with a replacement grow to contain the new text.
+    Extend,
+}
+
+/// Decides what happens to a span when all of its text is replaced or removed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DeletionPolicy {
+    /// The span is dropped.
+    #[default]
+    Remove,
+    /// The span becomes an empty span at the start of the edit.
+    Collapse,
+    /// The span becomes the span of the new text.
+    Cover,
+}
+
+/// Moves [`Span`]s through a sequence of [`TextEdit`]s.
+///
+/// Each edit is in terms of the text after the previous edits were applied.
+/// # Example
+/// ```rust
+/// # use wutil::span::{Span, SpanRebaser, TextEdit, Stickiness, DeletionPolicy};
+/// let text = "let foo = bar;";
+/// let foo = Span::from(4..7);
+/// let bar = Span::from(10..13);
+///
+/// // "let foo = bar;" -> "let mut foo = bar;" -> "let mut foo = 5;"
+/// let edits = [TextEdit::insert(4, 4), TextEdit::new(Span::from(14..17), 1)];
+///
+/// let rebaser = SpanRebaser::new(&edits);
+/// assert_eq!(rebaser.rebase(foo), Some(Span::from(8..11)));
+/// assert_eq!(rebaser.rebase(bar), None);
+///
+/// let rebaser = rebaser.with_deletion_policy(DeletionPolicy::Cover);
+/// assert_eq!(rebaser.rebase(bar), Some(Span::from(14..15)));
+///
+/// let rebaser = rebaser.with_stickiness(Stickiness::Extend);
+/// assert_eq!(rebaser.rebase(foo), Some(Span::from(4..11)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SpanRebaser<'a> {
+    edits: &'a [TextEdit],
+    stickiness: Stickiness,
+    deletion_policy: DeletionPolicy,
+}
+
+impl<'a> SpanRebaser<'a> {
+    pub fn new(edits: &'a [TextEdit]) -> Self {
+        Self {
+            edits,
+            stickiness: Stickiness::default(),
+            deletion_policy: DeletionPolicy::default(),
+        }
+    }
+
+    pub fn with_stickiness(self, stickiness: Stickiness) -> Self {
+        Self { stickiness, ..self }
+    }
+
+    pub fn with_deletion_policy(self, deletion_policy: DeletionPolicy) -> Self {
+        Self {
+            deletion_policy,
+            ..self
+        }
+    }
+
+    /// Moves a span through every edit. Returns `None` if the span was removed.
+    pub fn rebase(&self, span: Span) -> Option<Span> {
+        self.edits
+            .iter()
+            .try_fold(span, |span, edit| self.rebase_once(span, *edit))
+    }
+
+    /// Rebases every span in a `Vec`, removing the ones that did not survive.
+    #[cfg(feature = "alloc")]
+    pub fn retain_rebased(&self, spans: &mut Vec<Span>) {
+        spans.retain_mut(|span| match self.rebase(*span) {
+            Some(new) => {
+                *span = new;
+                true
+            }
+            None => false,
+        });
+    }
+
+    fn rebase_once(&self, span: Span, edit: TextEdit) -> Option<Span> {
+        let deleted = if span.is_empty() {
+            edit.span.start < span.start && span.start < edit.span.end
+        } else {
+            edit.span.contains_span(span)
+        };
+
+        if deleted {
+            return match self.deletion_policy {
+                DeletionPolicy::Remove => None,
+                DeletionPolicy::Collapse => Some(Span::at(edit.span.start)),
+                DeletionPolicy::Cover => Some(edit.new_span()),
+            };
+        }
+
+        let extend = self.stickiness == Stickiness::Extend;
+
+        if span.is_empty() && edit.span.is_empty() && span.start == edit.span.start {
+            return Some(if extend { edit.new_span() } else { span });
+        }
+
+        let start = edit.map(span.start, !extend);
+        let end = edit.map(span.end, extend);
+
+        Some(Span::from(start..end))
+    }
+}
+
+impl Span {
+    /// Moves a span through a [`TextEdit`] with the default [`Stickiness`] and [`DeletionPolicy`].
+    /// Returns `None` if all of the span's text was removed.
+    /// ```rust
+    /// # use wutil::span::{Span, TextEdit};
+    /// let span = Span::from(5..10);
+    ///
+    /// assert_eq!(span.rebase(TextEdit::insert(2, 3)), Some(Span::from(8..13)));
+    /// assert_eq!(span.rebase(TextEdit::insert(5, 3)), Some(Span::from(8..13)));
+    /// assert_eq!(span.rebase(TextEdit::insert(7, 3)), Some(Span::from(5..13)));
+    /// assert_eq!(span.rebase(TextEdit::insert(10, 3)), Some(Span::from(5..10)));
+    ///
+    /// assert_eq!(span.rebase(TextEdit::delete(Span::from(3..7))), Some(Span::from(3..6)));
+    /// assert_eq!(span.rebase(TextEdit::delete(Span::from(4..11))), None);
+    /// ```
+    pub fn rebase(self, edit: TextEdit) -> Option<Span> {
+        SpanRebaser::new(&[edit]).rebase(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replacements() {
+        let span = Span::from(5..10);
+        let exclude = SpanRebaser::new(&[]);
+        let extend = exclude.with_stickiness(Stickiness::Extend);
+
+        let some = |range: core::ops::Range<usize>| Some(Span::from(range));
+
+        // (edit, excluding result, extending result)
+        let tests = [
+            // before and after the span
+            (TextEdit::new(Span::from(0..2), 5), some(8..13), some(8..13)),
+            (
+                TextEdit::new(Span::from(12..14), 5),
+                some(5..10),
+                some(5..10),
+            ),
+            // touching the edges of the span
+            (TextEdit::new(Span::from(2..5), 1), some(3..8), some(3..8)),
+            (
+                TextEdit::new(Span::from(10..12), 1),
+                some(5..10),
+                some(5..10),
+            ),
+            // overlapping the start
+            (TextEdit::new(Span::from(3..7), 1), some(4..7), some(3..7)),
+            // overlapping the end
+            (TextEdit::new(Span::from(8..12), 1), some(5..8), some(5..9)),
+            // inside the span
+            (TextEdit::new(Span::from(6..8), 0), some(5..8), some(5..8)),
+            (TextEdit::new(Span::from(5..7), 4), some(5..12), some(5..12)),
+            // containing the span
+            (TextEdit::new(Span::from(4..11), 1), None, None),
+        ];
+
+        for (edit, excluded, extended) in tests {
+            let edits = [edit];
+            let exclude = SpanRebaser {
+                edits: &edits,
+                ..exclude
+            };
+            let extend = SpanRebaser {
+                edits: &edits,
+                ..extend
+            };
+
+            assert_eq!(exclude.rebase(span), excluded, "{edit:?}");
+            assert_eq!(extend.rebase(span), extended, "{edit:?}");
+        }
+    }
+
+    #[test]
+    fn empty_spans() {
+        let edits = [TextEdit::insert(5, 3)];
+        let rebaser = SpanRebaser::new(&edits);
+
+        assert_eq!(rebaser.rebase(Span::at(5)), Some(Span::at(5)));
+        assert_eq!(
+            rebaser
+                .with_stickiness(Stickiness::Extend)
+                .rebase(Span::at(5)),
+            Some(Span::from(5..8))
+        );
+
+        let edits = [TextEdit::new(Span::from(2..8), 1)];
+        let rebaser = SpanRebaser::new(&edits);
+
+        assert_eq!(rebaser.rebase(Span::at(2)), Some(Span::at(2)));
+        assert_eq!(rebaser.rebase(Span::at(8)), Some(Span::at(3)));
+        assert_eq!(rebaser.rebase(Span::at(5)), None);
+        assert_eq!(
+            rebaser
+                .with_deletion_policy(DeletionPolicy::Collapse)
+                .rebase(Span::at(5)),
+            Some(Span::at(2))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn retain_rebased() {
+        let edits = [TextEdit::delete(Span::from(0..3)), TextEdit::insert(0, 10)];
+        let mut spans = alloc::vec![Span::from(0..2), Span::from(2..5), Span::from(5..6)];
+
+        SpanRebaser::new(&edits).retain_rebased(&mut spans);
+
+        assert_eq!(spans, [Span::from(10..12), Span::from(12..13)]);
+    }
+}
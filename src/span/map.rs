@@ -0,0 +1,433 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Debug};
+
+use super::Span;
+
+/// A map from [`Span`]s to values that can quickly find every span covering a position or
+/// overlapping another span.
+///
+/// Entries are ordered by their start and then by their end. Internally, this is a treap where
+/// every node also stores the largest end within its subtree, so queries only visit subtrees that
+/// can contain a match.
+/// # Example
+/// ```rust
+/// # use wutil::span::{Span, SpanMap};
+/// let mut nodes = SpanMap::new();
+///
+/// nodes.insert(Span::from(0..20), "function");
+/// nodes.insert(Span::from(5..12), "block");
+/// nodes.insert(Span::from(6..9), "call");
+/// nodes.insert(Span::from(14..18), "return");
+///
+/// let at_cursor: Vec<&str> = nodes.stab(7).map(|(_, v)| *v).collect();
+/// assert_eq!(at_cursor, ["function", "block", "call"]);
+///
+/// let edited: Vec<&str> = nodes.overlapping(Span::from(10..15)).map(|(_, v)| *v).collect();
+/// assert_eq!(edited, ["function", "block", "return"]);
+/// ```
+#[derive(Clone)]
+pub struct SpanMap<V> {
+    root: Tree<V>,
+    len: usize,
+    seed: u64,
+}
+
+type Tree<V> = Option<Box<Node<V>>>;
+
+#[derive(Clone)]
+struct Node<V> {
+    span: Span,
+    value: V,
+    priority: u64,
+    /// The largest end of any span in this subtree.
+    max_end: usize,
+    left: Tree<V>,
+    right: Tree<V>,
+}
+
+impl<V> Node<V> {
+    fn key(&self) -> (usize, usize) {
+        (self.span.start, self.span.end)
+    }
+
+    fn update(&mut self) {
+        self.max_end = self.span.end;
+
+        for child in [&self.left, &self.right].into_iter().flatten() {
+            self.max_end = self.max_end.max(child.max_end);
+        }
+    }
+}
+
+impl<V> Default for SpanMap<V> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+}
+
+impl<V> SpanMap<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value. If the map already had a value for `span`, it is replaced and returned.
+    pub fn insert(&mut self, span: Span, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(span) {
+            return Some(core::mem::replace(old, value));
+        }
+
+        // xorshift64
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        let node = Box::new(Node {
+            span,
+            value,
+            priority: self.seed,
+            max_end: span.end,
+            left: None,
+            right: None,
+        });
+
+        let key = (span.start, span.end);
+        let (left, right) = split(self.root.take(), &|n| n.key() < key);
+        self.root = merge(merge(left, Some(node)), right);
+        self.len += 1;
+
+        None
+    }
+
+    /// Removes the value for `span` and returns it.
+    pub fn remove(&mut self, span: Span) -> Option<V> {
+        let key = (span.start, span.end);
+
+        let (left, rest) = split(self.root.take(), &|n| n.key() < key);
+        let (removed, right) = split(rest, &|n| n.key() <= key);
+        self.root = merge(left, right);
+
+        let removed = removed?;
+        self.len -= 1;
+
+        Some(removed.value)
+    }
+
+    pub fn get(&self, span: Span) -> Option<&V> {
+        let key = (span.start, span.end);
+        let mut node = self.root.as_deref();
+
+        while let Some(n) = node {
+            node = match key.cmp(&n.key()) {
+                core::cmp::Ordering::Less => n.left.as_deref(),
+                core::cmp::Ordering::Greater => n.right.as_deref(),
+                core::cmp::Ordering::Equal => return Some(&n.value),
+            };
+        }
+
+        None
+    }
+
+    pub fn get_mut(&mut self, span: Span) -> Option<&mut V> {
+        let key = (span.start, span.end);
+        let mut node = self.root.as_deref_mut();
+
+        while let Some(n) = node {
+            node = match key.cmp(&n.key()) {
+                core::cmp::Ordering::Less => n.left.as_deref_mut(),
+                core::cmp::Ordering::Greater => n.right.as_deref_mut(),
+                core::cmp::Ordering::Equal => return Some(&mut n.value),
+            };
+        }
+
+        None
+    }
+
+    pub fn contains_key(&self, span: Span) -> bool {
+        self.get(span).is_some()
+    }
+
+    /// Iterates over every entry whose span contains `pos`. Empty spans never contain a position.
+    pub fn stab(&self, pos: usize) -> SpanMapIter<'_, V> {
+        SpanMapIter::new(self, Query::Point(pos))
+    }
+
+    /// Iterates over every entry whose span [overlaps with](Span::overlaps_with) `span`.
+    pub fn overlapping(&self, span: Span) -> SpanMapIter<'_, V> {
+        SpanMapIter::new(self, Query::Overlapping(span))
+    }
+
+    /// Iterates over every entry in order of start and then end.
+    pub fn iter(&self) -> SpanMapIter<'_, V> {
+        SpanMapIter::new(self, Query::All)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+}
+
+/// Splits a tree into the nodes where `goes_left` returns `true` and the remaining nodes.
+/// `goes_left` must be `true` for a prefix of the nodes in order.
+fn split<V>(node: Tree<V>, goes_left: &impl Fn(&Node<V>) -> bool) -> (Tree<V>, Tree<V>) {
+    let Some(mut node) = node else {
+        return (None, None);
+    };
+
+    if goes_left(&node) {
+        let (left, right) = split(node.right.take(), goes_left);
+        node.right = left;
+        node.update();
+
+        (Some(node), right)
+    } else {
+        let (left, right) = split(node.left.take(), goes_left);
+        node.left = right;
+        node.update();
+
+        (left, Some(node))
+    }
+}
+
+/// Joins two trees where every node of `left` comes before every node of `right`.
+fn merge<V>(left: Tree<V>, right: Tree<V>) -> Tree<V> {
+    match (left, right) {
+        (None, node) | (node, None) => node,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+
+                Some(right)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Query {
+    All,
+    Point(usize),
+    Overlapping(Span),
+}
+
+impl Query {
+    fn matches(self, span: Span) -> bool {
+        match self {
+            Query::All => true,
+            Query::Point(pos) => span.contains(&pos),
+            Query::Overlapping(query) => span.overlaps_with(query),
+        }
+    }
+
+    /// Checks if a subtree with the given largest end can contain a match.
+    fn may_contain(self, max_end: usize) -> bool {
+        match self {
+            Query::All => true,
+            Query::Point(pos) => max_end > pos,
+            Query::Overlapping(query) => !query.is_empty() && max_end > query.start,
+        }
+    }
+
+    /// Checks if spans starting at or after `start` can match.
+    fn may_start_at(self, start: usize) -> bool {
+        match self {
+            Query::All => true,
+            Query::Point(pos) => start <= pos,
+            Query::Overlapping(query) => start < query.end,
+        }
+    }
+}
+
+/// Iterator returned by [`SpanMap::iter`], [`SpanMap::stab`], and [`SpanMap::overlapping`].
+pub struct SpanMapIter<'a, V> {
+    stack: Vec<&'a Node<V>>,
+    query: Query,
+}
+
+impl<'a, V> SpanMapIter<'a, V> {
+    fn new(map: &'a SpanMap<V>, query: Query) -> Self {
+        let mut iter = Self {
+            stack: Vec::new(),
+            query,
+        };
+
+        iter.push_left(map.root.as_deref());
+        iter
+    }
+
+    fn push_left(&mut self, mut node: Option<&'a Node<V>>) {
+        while let Some(n) = node {
+            if !self.query.may_contain(n.max_end) {
+                break;
+            }
+
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, V> Iterator for SpanMapIter<'a, V> {
+    type Item = (Span, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+
+            if self.query.may_start_at(node.span.start) {
+                self.push_left(node.right.as_deref());
+            } else {
+                // Every remaining node starts after this one.
+                self.stack.clear();
+            }
+
+            if self.query.matches(node.span) {
+                return Some((node.span, &node.value));
+            }
+        }
+    }
+}
+
+impl<V> Extend<(Span, V)> for SpanMap<V> {
+    fn extend<I: IntoIterator<Item = (Span, V)>>(&mut self, iter: I) {
+        for (span, value) in iter {
+            self.insert(span, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(Span, V)> for SpanMap<V> {
+    fn from_iter<I: IntoIterator<Item = (Span, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<V: Debug> Debug for SpanMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generates pseudo-random spans within `0..100`.
+    fn spans(count: usize) -> Vec<Span> {
+        let mut state = 12345u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) as usize % 100
+        };
+
+        (0..count)
+            .map(|_| {
+                let start = next();
+                let len = next() % 10;
+                Span::from(start..start + len)
+            })
+            .collect()
+    }
+
+    fn sorted(mut spans: Vec<Span>) -> Vec<Span> {
+        spans.sort_by_key(|s| (s.start, s.end));
+        spans.dedup();
+        spans
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let all = spans(300);
+        let mut map: SpanMap<usize> = all.iter().map(|s| (*s, s.start)).collect();
+
+        for removed in all.iter().step_by(3) {
+            map.remove(*removed);
+        }
+
+        let remaining: Vec<Span> = all
+            .iter()
+            .copied()
+            .filter(|s| !all.iter().step_by(3).any(|r| r == s))
+            .collect();
+        let remaining = sorted(remaining);
+
+        assert_eq!(map.len(), remaining.len());
+        assert_eq!(map.iter().map(|(s, _)| s).collect::<Vec<_>>(), remaining);
+
+        for pos in 0..110 {
+            let expected: Vec<Span> = remaining
+                .iter()
+                .copied()
+                .filter(|s| s.contains(&pos))
+                .collect();
+
+            assert_eq!(map.stab(pos).map(|(s, _)| s).collect::<Vec<_>>(), expected);
+        }
+
+        for query in spans(100).into_iter().chain([Span::at(0), Span::at(50)]) {
+            let expected: Vec<Span> = remaining
+                .iter()
+                .copied()
+                .filter(|s| s.overlaps_with(query))
+                .collect();
+
+            assert_eq!(
+                map.overlapping(query).map(|(s, _)| s).collect::<Vec<_>>(),
+                expected,
+                "{query:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn insert_replaces() {
+        let mut map = SpanMap::new();
+
+        assert_eq!(map.insert(Span::from(1..3), 'a'), None);
+        assert_eq!(map.insert(Span::from(1..3), 'b'), Some('a'));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(Span::from(1..3)), Some(&'b'));
+
+        assert_eq!(map.remove(Span::from(1..4)), None);
+        assert_eq!(map.remove(Span::from(1..3)), Some('b'));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn empty_spans() {
+        let map: SpanMap<()> = [(Span::at(5), ()), (Span::from(2..5), ())]
+            .into_iter()
+            .collect();
+
+        assert_eq!(map.stab(5).count(), 0);
+        assert_eq!(map.overlapping(Span::from(5..6)).count(), 0);
+        assert_eq!(map.overlapping(Span::at(5)).count(), 0);
+        assert_eq!(map.overlapping(Span::at(4)).count(), 0);
+        assert_eq!(
+            map.overlapping(Span::from(4..6))
+                .map(|(s, _)| s)
+                .collect::<Vec<_>>(),
+            [Span::from(2..5)]
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Contains [`serde`](::serde) support for spans.
+//!
+//! By default, spans are serialized as a struct with `start` and `end` fields. The [`compact`]
+//! module can be used with `#[serde(with = "...")]` to serialize them as a `(start, end)` tuple
+//! instead.
+//! # Example
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! # use wutil::Span;
+//! #[derive(Serialize, Deserialize)]
+//! struct Token {
+//!     kind: u8,
+//!     #[serde(with = "wutil::span::serde::compact")]
+//!     span: Span,
+//! }
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeStruct, SerializeTuple},
+};
+
+use super::{GenericSpan, SpanIndex, Spanned};
+
+const SPAN_FIELDS: &[&str] = &["start", "end"];
+const SPANNED_FIELDS: &[&str] = &["value", "span"];
+
+impl<Idx: SpanIndex + Serialize> Serialize for GenericSpan<Idx> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Span", 2)?;
+        state.serialize_field("start", &self.start)?;
+        state.serialize_field("end", &self.end)?;
+        state.end()
+    }
+}
+
+impl<'de, Idx: SpanIndex + Deserialize<'de>> Deserialize<'de> for GenericSpan<Idx> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("Span", SPAN_FIELDS, SpanVisitor(PhantomData))
+    }
+}
+
+/// Serializes a span as a `(start, end)` tuple.
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<Idx, S>(span: &GenericSpan<Idx>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        Idx: SpanIndex + Serialize,
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple(2)?;
+        state.serialize_element(&span.start)?;
+        state.serialize_element(&span.end)?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, Idx, D>(deserializer: D) -> Result<GenericSpan<Idx>, D::Error>
+    where
+        Idx: SpanIndex + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, SpanVisitor(PhantomData))
+    }
+}
+
+struct SpanVisitor<Idx>(PhantomData<Idx>);
+
+impl<'de, Idx: SpanIndex + Deserialize<'de>> Visitor<'de> for SpanVisitor<Idx> {
+    type Value = GenericSpan<Idx>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a span")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let start = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let end = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(GenericSpan::new(start, end))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut start = None;
+        let mut end = None;
+
+        while let Some(key) = map.next_key_seed(FieldSeed(SPAN_FIELDS))? {
+            let (slot, name) = match key {
+                Some(0) => (&mut start, "start"),
+                Some(_) => (&mut end, "end"),
+                None => {
+                    map.next_value::<de::IgnoredAny>()?;
+                    continue;
+                }
+            };
+
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(name));
+            }
+
+            *slot = Some(map.next_value()?);
+        }
+
+        let start = start.ok_or_else(|| de::Error::missing_field("start"))?;
+        let end = end.ok_or_else(|| de::Error::missing_field("end"))?;
+
+        Ok(GenericSpan::new(start, end))
+    }
+}
+
+/// Deserializes a struct field name into its index in a list of field names. Unknown fields are
+/// `None` so that they can be skipped, like with `#[derive(Deserialize)]`.
+struct FieldSeed(&'static [&'static str]);
+
+impl<'de> de::DeserializeSeed<'de> for FieldSeed {
+    type Value = Option<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor(self.0))
+    }
+}
+
+struct FieldVisitor(&'static [&'static str]);
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a field identifier")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(usize::try_from(v).ok().filter(|v| *v < self.0.len()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.0.iter().position(|f| *f == v))
+    }
+}
+
+impl<T: Serialize, Idx: SpanIndex + Serialize> Serialize for Spanned<T, Idx> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Spanned", 2)?;
+        state.serialize_field("value", &**self)?;
+        state.serialize_field("span", &self.span())?;
+        state.end()
+    }
+}
+
+impl<'de, T, Idx> Deserialize<'de> for Spanned<T, Idx>
+where
+    T: Deserialize<'de>,
+    Idx: SpanIndex + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("Spanned", SPANNED_FIELDS, SpannedVisitor(PhantomData))
+    }
+}
+
+struct SpannedVisitor<T, Idx>(PhantomData<(T, Idx)>);
+
+impl<'de, T, Idx> Visitor<'de> for SpannedVisitor<T, Idx>
+where
+    T: Deserialize<'de>,
+    Idx: SpanIndex + Deserialize<'de>,
+{
+    type Value = Spanned<T, Idx>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a spanned value")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let value = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let span = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(Spanned::new(value, span))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut value = None;
+        let mut span = None;
+
+        while let Some(key) = map.next_key_seed(FieldSeed(SPANNED_FIELDS))? {
+            match key {
+                Some(0) => {
+                    if value.is_some() {
+                        return Err(de::Error::duplicate_field("value"));
+                    }
+                    value = Some(map.next_value()?);
+                }
+                Some(_) => {
+                    if span.is_some() {
+                        return Err(de::Error::duplicate_field("span"));
+                    }
+                    span = Some(map.next_value()?);
+                }
+                None => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
+        let span = span.ok_or_else(|| de::Error::missing_field("span"))?;
+
+        Ok(Spanned::new(value, span))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::test_formats::{binary, json};
+    use crate::{
+        Span,
+        span::{GenericSpan, Spanned},
+    };
+
+    #[test]
+    fn span_json() {
+        let span = Span::from(2..5);
+
+        let text = json::to_string(&span).unwrap();
+        assert_eq!(text, r#"{"start":2,"end":5}"#);
+        assert_eq!(json::from_str::<Span>(&text).unwrap(), span);
+
+        let reordered: Span = json::from_str(r#"{"end":5,"start":2}"#).unwrap();
+        assert_eq!(reordered, span);
+
+        assert!(json::from_str::<Span>(r#"{"start":2}"#).is_err());
+        assert!(json::from_str::<Span>(r#"{"start":2,"end":5,"start":3}"#).is_err());
+    }
+
+    #[test]
+    fn span_compact() {
+        let span = GenericSpan::<u32>::new(7, 300);
+
+        let mut text = alloc::string::String::new();
+        super::compact::serialize(&span, &mut json::Serializer::new(&mut text)).unwrap();
+        assert_eq!(text, "[7,300]");
+
+        let parsed: GenericSpan<u32> =
+            super::compact::deserialize(&mut json::Deserializer::new(&text)).unwrap();
+        assert_eq!(parsed, span);
+
+        let mut bytes = alloc::vec::Vec::new();
+        super::compact::serialize(&span, &mut binary::Serializer::new(&mut bytes)).unwrap();
+        let parsed: GenericSpan<u32> =
+            super::compact::deserialize(&mut binary::Deserializer::new(&bytes)).unwrap();
+        assert_eq!(parsed, span);
+    }
+
+    #[test]
+    fn span_binary() {
+        let span = GenericSpan::<u64>::new(1, u64::MAX);
+
+        let bytes = binary::to_bytes(&span).unwrap();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(
+            binary::from_bytes::<GenericSpan<u64>>(&bytes).unwrap(),
+            span
+        );
+
+        assert!(binary::from_bytes::<GenericSpan<u8>>(&bytes).is_err());
+    }
+
+    #[test]
+    fn spanned() {
+        let spanned = Spanned::new("foo", Span::from(4..7));
+
+        let text = json::to_string(&spanned).unwrap();
+        assert_eq!(text, r#"{"value":"foo","span":{"start":4,"end":7}}"#);
+
+        let parsed: Spanned<&str> = json::from_str(&text).unwrap();
+        assert_eq!(parsed, spanned);
+
+        let duplicated = r#"{"value":"foo","span":{"start":4,"end":7},"value":"bar"}"#;
+        assert!(json::from_str::<Spanned<&str>>(duplicated).is_err());
+        assert!(json::from_str::<Spanned<&str>>(r#"{"value":"foo"}"#).is_err());
+
+        let unknown = r#"{"spam":[1,{"a":2}],"value":"foo","span":{"start":4,"end":7,"x":0}}"#;
+        assert_eq!(json::from_str::<Spanned<&str>>(unknown).unwrap(), spanned);
+
+        let bytes = binary::to_bytes(&spanned).unwrap();
+        let parsed: Spanned<&str> = binary::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, spanned);
+    }
+}
@@ -0,0 +1,264 @@
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Debug};
+
+use super::Span;
+
+/// A set of positions stored as a sorted list of disjoint spans.
+///
+/// Overlapping and adjacent spans are coalesced when inserted and empty spans are ignored, so
+/// iterating over a `SpanSet` always yields sorted, non-empty, non-adjacent spans.
+/// # Example
+/// ```rust
+/// # use wutil::span::{Span, SpanSet};
+/// let mut set = SpanSet::new();
+///
+/// set.insert(Span::from(0..4));
+/// set.insert(Span::from(8..10));
+/// set.insert(Span::from(4..6));
+/// set.remove(Span::from(2..3));
+///
+/// let spans: Vec<Span> = set.iter().collect();
+/// assert_eq!(spans, [Span::from(0..2), Span::from(3..6), Span::from(8..10)]);
+///
+/// assert!(set.contains(5));
+/// assert!(!set.contains(6));
+/// assert_eq!(set.span_at(4), Some(Span::from(3..6)));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SpanSet {
+    /// Maps the start of each span to its end.
+    spans: BTreeMap<usize, usize>,
+}
+
+impl SpanSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every position of `span` to the set.
+    pub fn insert(&mut self, span: Span) {
+        if span.is_empty() {
+            return;
+        }
+
+        let mut start = span.start;
+        let mut end = span.end;
+
+        if let Some((&prev_start, &prev_end)) = self.spans.range(..=start).next_back()
+            && prev_end >= start
+        {
+            start = prev_start;
+            end = end.max(prev_end);
+            self.spans.remove(&prev_start);
+        }
+
+        while let Some((&next_start, &next_end)) = self.spans.range(start..=end).next() {
+            end = end.max(next_end);
+            self.spans.remove(&next_start);
+        }
+
+        self.spans.insert(start, end);
+    }
+
+    /// Removes every position of `span` from the set.
+    pub fn remove(&mut self, span: Span) {
+        if span.is_empty() {
+            return;
+        }
+
+        if let Some((&prev_start, &prev_end)) = self.spans.range(..span.start).next_back()
+            && prev_end > span.start
+        {
+            self.spans.insert(prev_start, span.start);
+
+            if prev_end > span.end {
+                self.spans.insert(span.end, prev_end);
+                return;
+            }
+        }
+
+        while let Some((&next_start, &next_end)) = self.spans.range(span.start..span.end).next() {
+            self.spans.remove(&next_start);
+
+            if next_end > span.end {
+                self.spans.insert(span.end, next_end);
+            }
+        }
+    }
+
+    /// Checks if a position is in the set.
+    pub fn contains(&self, pos: usize) -> bool {
+        self.span_at(pos).is_some()
+    }
+
+    /// Gets the span of the set that contains `pos`.
+    pub fn span_at(&self, pos: usize) -> Option<Span> {
+        let (&start, &end) = self.spans.range(..=pos).next_back()?;
+
+        (pos < end).then(|| Span::from(start..end))
+    }
+
+    /// Checks if every position of `span` is in the set. Empty spans are always contained.
+    pub fn contains_span(&self, span: Span) -> bool {
+        span.is_empty() || self.span_at(span.start).is_some_and(|s| s.end >= span.end)
+    }
+
+    /// Checks if any position of `span` is in the set.
+    pub fn overlaps_with(&self, span: Span) -> bool {
+        if span.is_empty() {
+            return false;
+        }
+
+        self.contains(span.start) || self.spans.range(span.start..span.end).next().is_some()
+    }
+
+    /// Iterates over the spans of the set in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Span> + '_ {
+        self.spans
+            .iter()
+            .map(|(&start, &end)| Span::from(start..end))
+    }
+
+    /// Gets the number of disjoint spans in the set.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.spans.clear()
+    }
+}
+
+impl Extend<Span> for SpanSet {
+    fn extend<I: IntoIterator<Item = Span>>(&mut self, iter: I) {
+        for span in iter {
+            self.insert(span);
+        }
+    }
+}
+
+impl FromIterator<Span> for SpanSet {
+    fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Debug for SpanSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn spans(set: &SpanSet) -> Vec<Span> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert_coalesces() {
+        let mut set = SpanSet::new();
+
+        set.insert(Span::from(10..12));
+        set.insert(Span::from(0..2));
+        set.insert(Span::from(5..6));
+        assert_eq!(set.len(), 3);
+
+        // adjacent on both sides
+        set.insert(Span::from(2..5));
+        assert_eq!(spans(&set), [Span::from(0..6), Span::from(10..12)]);
+
+        // covers several spans
+        set.insert(Span::from(4..20));
+        assert_eq!(spans(&set), [Span::from(0..20)]);
+
+        // already contained
+        set.insert(Span::from(3..4));
+        assert_eq!(spans(&set), [Span::from(0..20)]);
+    }
+
+    #[test]
+    fn empty_spans() {
+        let mut set = SpanSet::new();
+
+        set.insert(Span::at(3));
+        assert!(set.is_empty());
+
+        set.insert(Span::from(0..5));
+        set.remove(Span::at(2));
+        assert_eq!(spans(&set), [Span::from(0..5)]);
+
+        assert!(set.contains_span(Span::at(100)));
+        assert!(!set.overlaps_with(Span::at(2)));
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set: SpanSet = [Span::from(0..10), Span::from(20..30)]
+            .into_iter()
+            .collect();
+
+        set.remove(Span::from(3..5));
+        assert_eq!(
+            spans(&set),
+            [Span::from(0..3), Span::from(5..10), Span::from(20..30)]
+        );
+
+        set.remove(Span::from(8..25));
+        assert_eq!(
+            spans(&set),
+            [Span::from(0..3), Span::from(5..8), Span::from(25..30)]
+        );
+
+        set.remove(Span::from(0..100));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let set: SpanSet = [Span::from(2..4), Span::from(6..8)].into_iter().collect();
+
+        assert!(!set.contains(1));
+        assert!(set.contains(2));
+        assert!(!set.contains(4));
+
+        assert!(set.contains_span(Span::from(6..8)));
+        assert!(!set.contains_span(Span::from(3..7)));
+
+        assert!(set.overlaps_with(Span::from(3..7)));
+        assert!(set.overlaps_with(Span::from(0..3)));
+        assert!(set.overlaps_with(Span::from(5..7)));
+        assert!(!set.overlaps_with(Span::from(4..6)));
+    }
+
+    #[test]
+    fn overlaps_matches_span() {
+        let all_spans =
+            || (0..6).flat_map(|start| (start..6).map(move |end| Span::from(start..end)));
+
+        for span in all_spans() {
+            let set: SpanSet = [span].into_iter().collect();
+
+            for query in all_spans() {
+                assert_eq!(
+                    set.overlaps_with(query),
+                    span.overlaps_with(query),
+                    "{span:?} {query:?}"
+                );
+                assert_eq!(
+                    span.intersection(query).is_some(),
+                    span.overlaps_with(query)
+                );
+            }
+        }
+    }
+}
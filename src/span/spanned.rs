@@ -0,0 +1,276 @@
+use core::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut, Index},
+};
+
+use super::{GenericSpan, SpanIndex};
+
+/// A value paired with the [`Span`](crate::Span) that it came from.
+///
+/// Equality and hashing take both the value and the span into account. Use
+/// [`Spanned::ignore_span`] to compare values regardless of where they came from.
+///
+/// `Spanned` implements `Deref<Target = T>`, so its inherent methods such as `map`, `span` and
+/// `as_ref` hide methods of the same name on `T`. Dereference explicitly to call those instead:
+/// ```rust
+/// # use wutil::span::{Span, Spanned};
+/// let maybe = Spanned::new(Some(2), Span::from(0..1));
+///
+/// assert!(maybe.map(|n| n.is_some()).into_inner());
+/// assert_eq!((*maybe).map(|n| n * 2), Some(4));
+/// ```
+/// # Example
+/// ```rust
+/// # use wutil::span::{Span, Spanned};
+/// let source = "let foo = 42;";
+///
+/// let name = Spanned::new("foo", Span::from(4..7));
+/// let value = Spanned::new(42, Span::from(10..12));
+///
+/// assert_eq!(name.len(), 3);
+/// assert_eq!(name.slice(source), "foo");
+/// assert_eq!(&source[value.span()], "42");
+///
+/// let binding = Spanned::join(name, value);
+/// assert_eq!(binding.slice(source), "foo = 42");
+/// assert_eq!(*binding, ("foo", 42));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Spanned<T, Idx: SpanIndex = usize> {
+    value: T,
+    span: GenericSpan<Idx>,
+}
+
+impl<T, Idx: SpanIndex> Spanned<T, Idx> {
+    pub fn new(value: T, span: GenericSpan<Idx>) -> Self {
+        Self { value, span }
+    }
+
+    pub fn span(&self) -> GenericSpan<Idx> {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: GenericSpan<Idx>) {
+        self.span = span;
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn into_parts(self) -> (T, GenericSpan<Idx>) {
+        (self.value, self.span)
+    }
+
+    pub fn as_ref(&self) -> Spanned<&T, Idx> {
+        Spanned::new(&self.value, self.span)
+    }
+
+    pub fn as_mut(&mut self) -> Spanned<&mut T, Idx> {
+        Spanned::new(&mut self.value, self.span)
+    }
+
+    /// Maps the value while keeping the span.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U, Idx> {
+        Spanned::new(f(self.value), self.span)
+    }
+
+    /// Maps the value with a fallible function. The error is given the span of the value.
+    /// ```rust
+    /// # use wutil::span::{Span, Spanned};
+    /// let number = Spanned::new("12a", Span::from(5..8));
+    ///
+    /// let err = number.try_map(|s| s.parse::<u32>()).unwrap_err();
+    /// assert_eq!(err.span(), Span::from(5..8));
+    /// ```
+    pub fn try_map<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<Spanned<U, Idx>, Spanned<E, Idx>> {
+        self.map(f).transpose()
+    }
+
+    /// Combines two values into a tuple spanning both of them.
+    pub fn join<U>(a: Self, b: Spanned<U, Idx>) -> Spanned<(T, U), Idx> {
+        Spanned::new((a.value, b.value), a.span.cover(b.span))
+    }
+
+    /// Gets the text or items that this value came from.
+    pub fn slice<'s, S>(&self, source: &'s S) -> &'s S::Output
+    where
+        S: Index<GenericSpan<Idx>> + ?Sized,
+    {
+        &source[self.span]
+    }
+
+    /// Gets a view of this value that ignores the span in comparisons and hashing.
+    /// ```rust
+    /// # use wutil::span::{Span, Spanned};
+    /// let a = Spanned::new("foo", Span::from(0..3));
+    /// let b = Spanned::new("foo", Span::from(10..13));
+    ///
+    /// assert!(a != b);
+    /// assert!(a.ignore_span() == b.ignore_span());
+    /// ```
+    pub fn ignore_span(&self) -> &IgnoreSpan<T, Idx> {
+        unsafe { crate::transmute_ref::<Self, IgnoreSpan<T, Idx>>(self) }
+    }
+}
+
+impl<T, E, Idx: SpanIndex> Spanned<Result<T, E>, Idx> {
+    /// Moves the span into both variants of a `Result`.
+    pub fn transpose(self) -> Result<Spanned<T, Idx>, Spanned<E, Idx>> {
+        match self.value {
+            Ok(value) => Ok(Spanned::new(value, self.span)),
+            Err(err) => Err(Spanned::new(err, self.span)),
+        }
+    }
+}
+
+impl<T, Idx: SpanIndex> Spanned<Option<T>, Idx> {
+    /// Moves the span into an `Option`.
+    pub fn transpose(self) -> Option<Spanned<T, Idx>> {
+        let span = self.span;
+        self.value.map(|value| Spanned::new(value, span))
+    }
+}
+
+/// A [`Spanned`] value that ignores its span in comparisons and hashing.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct IgnoreSpan<T, Idx: SpanIndex = usize>(pub Spanned<T, Idx>);
+
+impl<T, Idx: SpanIndex> Deref for IgnoreSpan<T, Idx> {
+    type Target = Spanned<T, Idx>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: PartialEq, Idx: SpanIndex> PartialEq for IgnoreSpan<T, Idx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.value == other.0.value
+    }
+}
+
+impl<T: Eq, Idx: SpanIndex> Eq for IgnoreSpan<T, Idx> {}
+
+impl<T: Hash, Idx: SpanIndex> Hash for IgnoreSpan<T, Idx> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.value.hash(state);
+    }
+}
+
+impl<T: Debug, Idx: SpanIndex> Debug for IgnoreSpan<T, Idx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.value.fmt(f)
+    }
+}
+
+// Boilerplate //
+
+impl<T, Idx: SpanIndex> Deref for Spanned<T, Idx> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, Idx: SpanIndex> DerefMut for Spanned<T, Idx> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T, Idx: SpanIndex> From<(T, GenericSpan<Idx>)> for Spanned<T, Idx> {
+    fn from((value, span): (T, GenericSpan<Idx>)) -> Self {
+        Self::new(value, span)
+    }
+}
+
+impl<T, Idx: SpanIndex> From<Spanned<T, Idx>> for (T, GenericSpan<Idx>) {
+    fn from(value: Spanned<T, Idx>) -> Self {
+        value.into_parts()
+    }
+}
+
+impl<T: PartialEq, Idx: SpanIndex> PartialEq for Spanned<T, Idx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.span == other.span
+    }
+}
+
+impl<T: Eq, Idx: SpanIndex> Eq for Spanned<T, Idx> {}
+
+impl<T: Hash, Idx: SpanIndex + Hash> Hash for Spanned<T, Idx> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.span.hash(state);
+    }
+}
+
+impl<T: Debug, Idx: SpanIndex + Debug> Debug for Spanned<T, Idx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spanned")
+            .field("value", &self.value)
+            .field("span", &self.span)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::hash::{Hash, Hasher};
+    use std::hash::DefaultHasher;
+
+    use super::*;
+    use crate::Span;
+
+    fn hash_of(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn map_keeps_span() {
+        let number = Spanned::new("42", Span::from(3..5));
+        let parsed = number.map(|s| s.parse::<u32>().unwrap());
+
+        assert_eq!(parsed, Spanned::new(42, Span::from(3..5)));
+        assert_eq!(
+            parsed.as_ref().map(|n| n + 1).into_parts(),
+            (43, Span::from(3..5))
+        );
+    }
+
+    #[test]
+    fn ignore_span_views_same_value() {
+        let spanned = Spanned::new(['a', 'b'], Span::from(7..9));
+        let ignored = spanned.ignore_span();
+
+        assert!(core::ptr::eq(&ignored.0, &spanned));
+        assert_eq!(ignored.span(), Span::from(7..9));
+        assert_eq!(*ignored.0, ['a', 'b']);
+    }
+
+    #[test]
+    fn ignore_span_comparison() {
+        let a = Spanned::new("foo", Span::from(0..3));
+        let b = Spanned::new("foo", Span::from(10..13));
+        let c = Spanned::new("bar", Span::from(0..3));
+
+        assert_ne!(a, b);
+        assert_ne!(hash_of(&a), hash_of(&b));
+
+        assert_eq!(a.ignore_span(), b.ignore_span());
+        assert_eq!(hash_of(a.ignore_span()), hash_of(b.ignore_span()));
+
+        assert_ne!(a.ignore_span(), c.ignore_span());
+    }
+}
@@ -1,6 +1,17 @@
 //! Contains extentions for rust `str`.
 
-use crate::span::Span;
+use crate::{slice::SliceExt, span::Span};
+
+pub(crate) mod lines;
+#[cfg(feature = "unicode")]
+pub(crate) mod unicode;
+mod utf16;
+pub use lines::{LineSpans, LineSpansInclusive, LinesWithSpans};
+#[cfg(feature = "unicode")]
+pub use unicode::{GraphemeSpans, UNICODE_VERSION};
+pub use utf16::Utf16Error;
+#[cfg(feature = "alloc")]
+pub use utf16::Utf16Index;
 
 pub trait StrExt {
     /// Gets the position of a substring within a string.
@@ -15,25 +26,238 @@ pub trait StrExt {
     /// assert_eq!(place.substr_pos(second_is), Some(Span::from(4..6)));
     /// ```
     fn substr_pos(&self, substr: &Self) -> Option<Span>;
+    /// Splits a string into the text before, inside, and after a span. Returns `None` if the span
+    /// is out of bounds, inverted, or does not lie on char boundaries.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let text = "let x = 5;";
+    ///
+    /// assert_eq!(text.split_at_span(Span::from(4..5)), Some(("let ", "x", " = 5;")));
+    /// assert_eq!(text.split_at_span(Span::from(4..11)), None);
+    /// ```
+    fn split_at_span(&self, span: Span) -> Option<(&str, &str, &str)>;
+    /// Like [`split_at_span`](StrExt::split_at_span), but panics if the span is not a valid span
+    /// of the string.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let text = "let x = 5;";
+    /// let value = text.split(' ').nth(3).unwrap();
+    ///
+    /// let (before, _, after) = text.slice_around(text.substr_pos(value).unwrap());
+    /// assert_eq!((before, after), ("let x = ", ""));
+    /// ```
+    fn slice_around(&self, span: Span) -> (&str, &str, &str);
     /// Gets the length of the character starting at `byte_index`
     fn char_length(&self, byte_index: usize) -> Option<usize>;
     /// Gets the range of the character starting at `byte_index`
     fn char_span(&self, byte_index: usize) -> Option<Span>;
+    /// Gets the span of every line excluding its line terminator. Lines are terminated by `\n`,
+    /// `\r\n`, or a lone `\r`. Like [`str::lines`], a trailing line terminator does not start an
+    /// extra empty line.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let text = "foo\r\nbar\rbaz\n";
+    ///
+    /// let lines: Vec<Span> = text.line_spans().collect();
+    /// assert_eq!(lines, [Span::from(0..3), Span::from(5..8), Span::from(9..12)]);
+    /// ```
+    fn line_spans(&self) -> LineSpans<'_>;
+    /// Gets the span of every line including its line terminator.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let text = "foo\r\nbar\rbaz";
+    ///
+    /// let lines: Vec<Span> = text.line_spans_inclusive().collect();
+    /// assert_eq!(lines, [Span::from(0..5), Span::from(5..9), Span::from(9..12)]);
+    /// ```
+    fn line_spans_inclusive(&self) -> LineSpansInclusive<'_>;
+    /// Gets every line along with its span, excluding line terminators.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let mut lines = "foo\nbar".lines_with_spans();
+    ///
+    /// assert_eq!(lines.next(), Some((Span::from(0..3), "foo")));
+    /// assert_eq!(lines.next(), Some((Span::from(4..7), "bar")));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    fn lines_with_spans(&self) -> LinesWithSpans<'_>;
+    /// Gets the span of the line containing `byte_index`, excluding its line terminator. The end of
+    /// the string is part of the last line, which is empty if the string ends with a line
+    /// terminator. Unlike [`line_spans`](StrExt::line_spans), a trailing line terminator does
+    /// start an extra empty line here, so that every index up to the length of the string has a
+    /// line. This matches [`SourceFile`](crate::source_map::SourceFile).
+    ///
+    /// This scans from `byte_index` to both ends of its line. To look up many indices, use a
+    /// [`SourceFile`](crate::source_map::SourceFile), which indexes the lines once.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let text = "foo\r\nbar\n";
+    ///
+    /// assert_eq!(text.line_span_at(1), Some(Span::from(0..3)));
+    /// assert_eq!(text.line_span_at(4), Some(Span::from(0..3)));
+    /// assert_eq!(text.line_span_at(5), Some(Span::from(5..8)));
+    /// assert_eq!(text.line_span_at(9), Some(Span::at(9)));
+    /// assert_eq!(text.line_span_at(10), None);
+    /// ```
+    fn line_span_at(&self, byte_index: usize) -> Option<Span>;
+    /// Gets the zero-based number of the line containing `byte_index`. Lines are numbered like
+    /// [`line_span_at`](StrExt::line_span_at), so the end of a string with a trailing line
+    /// terminator is on an extra empty line that [`line_spans`](StrExt::line_spans) doesn't
+    /// yield.
+    ///
+    /// This counts the line terminators before `byte_index`, so it takes linear time. To look up
+    /// many indices, use a [`SourceFile`](crate::source_map::SourceFile), which indexes the lines
+    /// once.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let text = "foo\r\nbar\rbaz";
+    ///
+    /// assert_eq!(text.line_number_at(4), Some(0));
+    /// assert_eq!(text.line_number_at(5), Some(1));
+    /// assert_eq!(text.line_number_at(9), Some(2));
+    /// assert_eq!(text.line_number_at(20), None);
+    ///
+    /// assert_eq!("a\nb\n".line_spans().count(), 2);
+    /// assert_eq!("a\nb\n".line_number_at(4), Some(2));
+    /// ```
+    fn line_number_at(&self, byte_index: usize) -> Option<usize>;
+    /// Gets the span of the extended grapheme cluster containing `byte_index`.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let text = "ne\u{301}e"; // né with a combining accent
+    ///
+    /// assert_eq!(text.char_span(1), Some(Span::from(1..2)));
+    /// assert_eq!(text.grapheme_span(1), Some(Span::from(1..4)));
+    /// assert_eq!(text.grapheme_span(2), Some(Span::from(1..4)));
+    /// assert_eq!(text.grapheme_span(5), None);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn grapheme_span(&self, byte_index: usize) -> Option<Span>;
+    /// Gets every extended grapheme cluster along with its span.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::span::Span;
+    /// let mut graphemes = "a🇫🇷\r\n".grapheme_indices_spans();
+    ///
+    /// assert_eq!(graphemes.next(), Some((Span::from(0..1), "a")));
+    /// assert_eq!(graphemes.next(), Some((Span::from(1..9), "🇫🇷")));
+    /// assert_eq!(graphemes.next(), Some((Span::from(9..11), "\r\n")));
+    /// assert_eq!(graphemes.next(), None);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn grapheme_indices_spans(&self) -> GraphemeSpans<'_>;
+    /// Gets the number of terminal columns needed to display a string. Control characters
+    /// (including line terminators) have no width.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// assert_eq!("abc".display_width(), 3);
+    /// assert_eq!("日本".display_width(), 4);
+    /// assert_eq!("e\u{301}".display_width(), 1);
+    /// assert_eq!("👩‍🔬".display_width(), 2);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn display_width(&self) -> usize;
+    /// Gets the byte index of the grapheme cluster displayed at the zero-based column `column`.
+    /// If the column is in the middle of a wide grapheme cluster, its start is returned. Returns
+    /// `None` if the column is past the end of the string.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let text = "a日b";
+    ///
+    /// assert_eq!(text.column_to_byte(1), Some(1));
+    /// assert_eq!(text.column_to_byte(2), Some(1));
+    /// assert_eq!(text.column_to_byte(3), Some(4));
+    /// assert_eq!(text.column_to_byte(4), Some(5));
+    /// assert_eq!(text.column_to_byte(5), None);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn column_to_byte(&self, column: usize) -> Option<usize>;
+    /// Gets the zero-based column at which the grapheme cluster containing `byte_index` is
+    /// displayed. Returns `None` if the index is past the end of the string.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// let text = "a日b";
+    ///
+    /// assert_eq!(text.byte_to_column(1), Some(1));
+    /// assert_eq!(text.byte_to_column(2), Some(1));
+    /// assert_eq!(text.byte_to_column(4), Some(3));
+    /// assert_eq!(text.byte_to_column(5), Some(4));
+    /// assert_eq!(text.byte_to_column(6), None);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn byte_to_column(&self, byte_index: usize) -> Option<usize>;
+    /// Converts a byte offset into an offset in UTF-16 code units. Use [`Utf16Index`] to convert
+    /// many offsets of the same string.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::str::Utf16Error;
+    /// let text = "h\u{e9}\u{1f600}!";
+    ///
+    /// assert_eq!(text.utf16_offset_of(3), Ok(2));
+    /// assert_eq!(text.utf16_offset_of(7), Ok(4));
+    /// assert_eq!(text.utf16_offset_of(2), Err(Utf16Error::NotCharBoundary));
+    /// assert_eq!(text.utf16_offset_of(9), Err(Utf16Error::OutOfBounds));
+    /// ```
+    fn utf16_offset_of(&self, byte_index: usize) -> Result<usize, Utf16Error>;
+    /// Converts an offset in UTF-16 code units into a byte offset.
+    /// # Example
+    /// ```rust
+    /// # use wutil::prelude::*;
+    /// # use wutil::str::Utf16Error;
+    /// let text = "h\u{e9}\u{1f600}!";
+    ///
+    /// assert_eq!(text.byte_offset_of_utf16(2), Ok(3));
+    /// assert_eq!(text.byte_offset_of_utf16(4), Ok(7));
+    /// assert_eq!(text.byte_offset_of_utf16(3), Err(Utf16Error::InsideSurrogatePair));
+    /// assert_eq!(text.byte_offset_of_utf16(6), Err(Utf16Error::OutOfBounds));
+    /// ```
+    fn byte_offset_of_utf16(&self, utf16_index: usize) -> Result<usize, Utf16Error>;
 }
 
 impl StrExt for str {
     fn substr_pos(&self, substr: &Self) -> Option<Span> {
-        let self_start = self.as_ptr() as usize;
-        let substr_start = substr.as_ptr() as usize;
-
-        let start = substr_start.wrapping_sub(self_start);
-        let end = start + substr.len();
+        self.as_bytes().subslice_pos(substr.as_bytes())
+    }
 
-        if start > self.len() || end > self.len() {
+    fn split_at_span(&self, span: Span) -> Option<(&str, &str, &str)> {
+        if span.start > span.end {
             return None;
         }
 
-        Some(Span::from(start..end))
+        Some((
+            self.get(..span.start)?,
+            self.get(span.start..span.end)?,
+            self.get(span.end..)?,
+        ))
+    }
+
+    fn slice_around(&self, span: Span) -> (&str, &str, &str) {
+        match self.split_at_span(span) {
+            Some(parts) => parts,
+            None => panic!(
+                "span {span} is not a valid span of a string of length {}",
+                self.len()
+            ),
+        }
     }
 
     fn char_length(&self, byte_index: usize) -> Option<usize> {
@@ -47,4 +271,57 @@ impl StrExt for str {
     fn char_span(&self, byte_index: usize) -> Option<Span> {
         Some(Span::at(byte_index).with_len(self.char_length(byte_index)?))
     }
+
+    fn line_spans(&self) -> LineSpans<'_> {
+        LineSpans::new(self)
+    }
+
+    fn line_spans_inclusive(&self) -> LineSpansInclusive<'_> {
+        LineSpansInclusive::new(self)
+    }
+
+    fn lines_with_spans(&self) -> LinesWithSpans<'_> {
+        LinesWithSpans::new(self)
+    }
+
+    fn line_span_at(&self, byte_index: usize) -> Option<Span> {
+        lines::line_span_at(self, byte_index)
+    }
+
+    fn line_number_at(&self, byte_index: usize) -> Option<usize> {
+        lines::line_number_at(self, byte_index)
+    }
+
+    #[cfg(feature = "unicode")]
+    fn grapheme_span(&self, byte_index: usize) -> Option<Span> {
+        unicode::grapheme_span(self, byte_index)
+    }
+
+    #[cfg(feature = "unicode")]
+    fn grapheme_indices_spans(&self) -> GraphemeSpans<'_> {
+        GraphemeSpans::new(self)
+    }
+
+    #[cfg(feature = "unicode")]
+    fn display_width(&self) -> usize {
+        unicode::display_width(self)
+    }
+
+    #[cfg(feature = "unicode")]
+    fn column_to_byte(&self, column: usize) -> Option<usize> {
+        unicode::column_to_byte(self, column)
+    }
+
+    #[cfg(feature = "unicode")]
+    fn byte_to_column(&self, byte_index: usize) -> Option<usize> {
+        unicode::byte_to_column(self, byte_index)
+    }
+
+    fn utf16_offset_of(&self, byte_index: usize) -> Result<usize, Utf16Error> {
+        utf16::utf16_offset_of(self, byte_index)
+    }
+
+    fn byte_offset_of_utf16(&self, utf16_index: usize) -> Result<usize, Utf16Error> {
+        utf16::byte_offset_of_utf16(self, utf16_index)
+    }
 }
@@ -0,0 +1,187 @@
+use core::iter::FusedIterator;
+
+use crate::span::Span;
+
+/// An iterator over the spans of the lines of a string, excluding line terminators.
+///
+/// This `struct` is created by [`StrExt::line_spans`](super::StrExt::line_spans).
+#[derive(Clone)]
+pub struct LineSpans<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineSpans<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    /// Gets the next line without and with its terminator.
+    fn next_line(&mut self) -> Option<(Span, Span)> {
+        if self.pos >= self.text.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        let (end, next) = line_end(self.text.as_bytes(), start);
+        self.pos = next;
+
+        Some((Span::from(start..end), Span::from(start..next)))
+    }
+}
+
+impl Iterator for LineSpans<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().map(|(line, _)| line)
+    }
+}
+
+impl FusedIterator for LineSpans<'_> {}
+
+/// An iterator over the spans of the lines of a string, including line terminators.
+///
+/// This `struct` is created by
+/// [`StrExt::line_spans_inclusive`](super::StrExt::line_spans_inclusive).
+#[derive(Clone)]
+pub struct LineSpansInclusive<'a> {
+    inner: LineSpans<'a>,
+}
+
+impl<'a> LineSpansInclusive<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self {
+            inner: LineSpans::new(text),
+        }
+    }
+}
+
+impl Iterator for LineSpansInclusive<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_line().map(|(_, line)| line)
+    }
+}
+
+impl FusedIterator for LineSpansInclusive<'_> {}
+
+/// An iterator over the lines of a string and their spans, excluding line terminators.
+///
+/// This `struct` is created by [`StrExt::lines_with_spans`](super::StrExt::lines_with_spans).
+#[derive(Clone)]
+pub struct LinesWithSpans<'a> {
+    inner: LineSpans<'a>,
+}
+
+impl<'a> LinesWithSpans<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self {
+            inner: LineSpans::new(text),
+        }
+    }
+}
+
+impl<'a> Iterator for LinesWithSpans<'a> {
+    type Item = (Span, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = self.inner.next()?;
+
+        Some((span, &self.inner.text[span]))
+    }
+}
+
+impl FusedIterator for LinesWithSpans<'_> {}
+
+/// Finds the end of the line starting at `start`. Returns the end of the line's text and the start
+/// of the next line. This is the one place where lines are split, so that every line API agrees on
+/// what a line terminator is.
+pub(crate) fn line_end(bytes: &[u8], start: usize) -> (usize, usize) {
+    let Some(offset) = bytes[start..]
+        .iter()
+        .position(|b| matches!(b, b'\n' | b'\r'))
+    else {
+        return (bytes.len(), bytes.len());
+    };
+
+    let end = start + offset;
+
+    if bytes[end] == b'\r' && bytes.get(end + 1) == Some(&b'\n') {
+        (end, end + 2)
+    } else {
+        (end, end + 1)
+    }
+}
+
+/// Checks if the byte at `index` is the last byte of a line terminator.
+fn ends_line(bytes: &[u8], index: usize) -> bool {
+    match bytes[index] {
+        b'\n' => true,
+        b'\r' => bytes.get(index + 1) != Some(&b'\n'),
+        _ => false,
+    }
+}
+
+pub(super) fn line_span_at(text: &str, byte_index: usize) -> Option<Span> {
+    let bytes = text.as_bytes();
+
+    if byte_index > bytes.len() {
+        return None;
+    }
+
+    let start = (0..byte_index)
+        .rev()
+        .find(|i| ends_line(bytes, *i))
+        .map_or(0, |i| i + 1);
+
+    Some(Span::from(start..line_end(bytes, start).0))
+}
+
+pub(super) fn line_number_at(text: &str, byte_index: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    if byte_index > bytes.len() {
+        return None;
+    }
+
+    Some((0..byte_index).filter(|i| ends_line(bytes, *i)).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::str::StrExt;
+
+    #[test]
+    fn terminators() {
+        let text = "a\nbc\r\n\rd\r\n";
+
+        let lines: [Span; 4] = [0..1, 2..4, 6..6, 7..8].map(Span::from);
+        let inclusive: [Span; 4] = [0..2, 2..6, 6..7, 7..10].map(Span::from);
+
+        assert!(text.line_spans().eq(lines));
+        assert!(text.line_spans_inclusive().eq(inclusive));
+
+        for (number, line) in inclusive.into_iter().enumerate() {
+            for byte_index in line {
+                assert_eq!(text.line_number_at(byte_index), Some(number));
+                assert_eq!(text.line_span_at(byte_index), Some(lines[number]));
+            }
+        }
+
+        assert_eq!(text.line_number_at(text.len()), Some(4));
+        assert_eq!(text.line_span_at(text.len()), Some(Span::at(text.len())));
+        assert_eq!(text.line_span_at(text.len() + 1), None);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!("".line_spans().next(), None);
+        assert_eq!("".line_span_at(0), Some(Span::at(0)));
+        assert_eq!("".line_number_at(0), Some(0));
+
+        assert!("\n\n".line_spans().eq([Span::at(0), Span::at(1)]));
+    }
+}
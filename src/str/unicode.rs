@@ -0,0 +1,245 @@
+use core::{cmp::Ordering, iter::FusedIterator};
+
+use crate::span::Span;
+
+mod tables;
+pub use tables::UNICODE_VERSION;
+
+/// The `Grapheme_Cluster_Break` property of a character. `Extended_Pictographic` and
+/// `Indic_Conjunct_Break=Consonant` are included as categories because no character with those
+/// properties has a `Grapheme_Cluster_Break` value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum GraphemeCategory {
+    Any,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    ExtendedPictographic,
+    InCbConsonant,
+}
+
+use GraphemeCategory::*;
+
+fn search<T: Copy>(table: &[(char, char, T)], c: char) -> Option<T> {
+    table
+        .binary_search_by(|&(lo, hi, _)| {
+            if c < lo {
+                Ordering::Greater
+            } else if c > hi {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|i| table[i].2)
+}
+
+fn category(c: char) -> GraphemeCategory {
+    search(tables::GRAPHEME_CATEGORIES, c).unwrap_or(Any)
+}
+
+fn is_incb_extend(c: char) -> bool {
+    tables::INCB_EXTEND
+        .binary_search_by(|&(lo, hi)| {
+            if c < lo {
+                Ordering::Greater
+            } else if c > hi {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+fn is_incb_linker(c: char) -> bool {
+    tables::INCB_LINKERS.binary_search(&c).is_ok()
+}
+
+/// The display width of a single character. Control characters have no width.
+pub(crate) fn char_width(c: char) -> usize {
+    search(tables::WIDTHS, c).map_or(1, usize::from)
+}
+
+/// Finds the end of the grapheme cluster starting at `start` using the rules from
+/// [UAX #29](https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundary_Rules).
+/// `start` must be a grapheme cluster boundary.
+fn grapheme_end(s: &str, start: usize) -> usize {
+    let mut chars = s[start..].char_indices();
+
+    let Some((_, first)) = chars.next() else {
+        return start;
+    };
+
+    let mut prev = category(first);
+    // The number of regional indicators directly before the current character
+    let mut regional_indicators = usize::from(prev == RegionalIndicator);
+    // Whether the previous characters match `\p{Extended_Pictographic} Extend*`
+    let mut pictographic = prev == ExtendedPictographic;
+    // Whether the previous characters match `\p{Extended_Pictographic} Extend* ZWJ`
+    let mut pictographic_zwj = false;
+    // `Some(has_linker)` if the previous characters match
+    // `\p{InCB=Consonant} [\p{InCB=Extend}\p{InCB=Linker}]*`
+    let mut conjunct = (prev == InCbConsonant).then_some(false);
+
+    for (offset, c) in chars {
+        let cur = category(c);
+
+        let joined = match (prev, cur) {
+            (Cr, Lf) => true,                                                       // GB3
+            (Cr | Lf | Control, _) | (_, Cr | Lf | Control) => false,               // GB4, GB5
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,         // GB6 - GB8
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => true,                 // GB9 - GB9b
+            (_, InCbConsonant) if conjunct == Some(true) => true,                   // GB9c
+            (Zwj, ExtendedPictographic) => pictographic_zwj,                        // GB11
+            (RegionalIndicator, RegionalIndicator) => regional_indicators % 2 == 1, // GB12, GB13
+            _ => false,                                                             // GB999
+        };
+
+        if !joined {
+            return start + offset;
+        }
+
+        regional_indicators = if cur == RegionalIndicator {
+            regional_indicators + 1
+        } else {
+            0
+        };
+
+        pictographic_zwj = pictographic && cur == Zwj;
+        pictographic = cur == ExtendedPictographic || pictographic && cur == Extend;
+
+        conjunct = if cur == InCbConsonant {
+            Some(false)
+        } else if is_incb_linker(c) {
+            conjunct.map(|_| true)
+        } else if is_incb_extend(c) {
+            conjunct
+        } else {
+            None
+        };
+
+        prev = cur;
+    }
+
+    s.len()
+}
+
+/// Gets the display width of a grapheme cluster.
+fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+
+    let emoji_presentation = first.is_ascii_digit()
+        || matches!(first, '#' | '*')
+        || category(first) == ExtendedPictographic;
+
+    if emoji_presentation && grapheme.contains('\u{FE0F}') {
+        return 2;
+    }
+
+    if category(first) == RegionalIndicator && chars.next().is_some() {
+        return 2;
+    }
+
+    grapheme.chars().map(char_width).max().unwrap_or(0)
+}
+
+/// Finds a grapheme cluster boundary at or before `byte_index`.
+fn boundary_before(s: &str, byte_index: usize) -> usize {
+    // Line terminators are always followed by a boundary
+    super::lines::line_span_at(s, byte_index).map_or(0, |line| line.start)
+}
+
+/// An iterator over the grapheme clusters of a string and their spans.
+///
+/// This `struct` is created by
+/// [`StrExt::grapheme_indices_spans`](super::StrExt::grapheme_indices_spans).
+#[derive(Clone)]
+pub struct GraphemeSpans<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> GraphemeSpans<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn starting_at(text: &'a str, pos: usize) -> Self {
+        Self { text, pos }
+    }
+}
+
+impl<'a> Iterator for GraphemeSpans<'a> {
+    type Item = (Span, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.text.len() {
+            return None;
+        }
+
+        let span = Span::from(self.pos..grapheme_end(self.text, self.pos));
+        self.pos = span.end;
+
+        Some((span, &self.text[span]))
+    }
+}
+
+impl FusedIterator for GraphemeSpans<'_> {}
+
+pub(super) fn grapheme_span(s: &str, byte_index: usize) -> Option<Span> {
+    if byte_index >= s.len() {
+        return None;
+    }
+
+    GraphemeSpans::starting_at(s, boundary_before(s, byte_index))
+        .map(|(span, _)| span)
+        .find(|span| span.contains(&byte_index))
+}
+
+pub(super) fn display_width(s: &str) -> usize {
+    GraphemeSpans::new(s).map(|(_, g)| grapheme_width(g)).sum()
+}
+
+pub(super) fn column_to_byte(s: &str, column: usize) -> Option<usize> {
+    let mut current = 0;
+
+    for (span, grapheme) in GraphemeSpans::new(s) {
+        let width = grapheme_width(grapheme);
+
+        if column < current + width || column == current {
+            return Some(span.start);
+        }
+
+        current += width;
+    }
+
+    (column == current).then_some(s.len())
+}
+
+pub(super) fn byte_to_column(s: &str, byte_index: usize) -> Option<usize> {
+    if byte_index > s.len() {
+        return None;
+    }
+
+    Some(
+        GraphemeSpans::new(s)
+            .take_while(|(span, _)| span.end <= byte_index)
+            .map(|(_, g)| grapheme_width(g))
+            .sum(),
+    )
+}
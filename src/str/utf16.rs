@@ -0,0 +1,237 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+#[cfg(feature = "alloc")]
+use crate::span::Span;
+
+/// An error returned when converting between UTF-8 and UTF-16 offsets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Utf16Error {
+    /// The offset is past the end of the string.
+    OutOfBounds,
+    /// The byte offset is in the middle of a UTF-8 sequence.
+    NotCharBoundary,
+    /// The UTF-16 offset is between the two halves of a surrogate pair.
+    InsideSurrogatePair,
+}
+
+impl Display for Utf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::OutOfBounds => "offset is out of bounds",
+            Self::NotCharBoundary => "byte offset is not a char boundary",
+            Self::InsideSurrogatePair => "UTF-16 offset is inside of a surrogate pair",
+        })
+    }
+}
+
+impl core::error::Error for Utf16Error {}
+
+pub(super) fn utf16_offset_of(s: &str, byte_index: usize) -> Result<usize, Utf16Error> {
+    let prefix = s.get(..byte_index).ok_or(if byte_index > s.len() {
+        Utf16Error::OutOfBounds
+    } else {
+        Utf16Error::NotCharBoundary
+    })?;
+
+    Ok(prefix.chars().map(char::len_utf16).sum())
+}
+
+pub(super) fn byte_offset_of_utf16(s: &str, utf16_index: usize) -> Result<usize, Utf16Error> {
+    let mut utf16 = 0;
+
+    for (byte, c) in s.char_indices() {
+        if utf16 >= utf16_index {
+            break;
+        }
+
+        utf16 += c.len_utf16();
+
+        if utf16 > utf16_index {
+            return Err(Utf16Error::InsideSurrogatePair);
+        }
+
+        if utf16 == utf16_index {
+            return Ok(byte + c.len_utf8());
+        }
+    }
+
+    if utf16 == utf16_index {
+        Ok(0)
+    } else {
+        Err(Utf16Error::OutOfBounds)
+    }
+}
+
+/// A precomputed index for converting between UTF-8 and UTF-16 offsets of a string, such as LSP
+/// positions.
+///
+/// Building the index takes one pass over the string, and each conversion takes O(log n) time in
+/// the number of non-ASCII characters.
+/// # Example
+/// ```rust
+/// # use wutil::str::{Utf16Index, Utf16Error};
+/// # use wutil::span::Span;
+/// let text = "a😀b";
+/// let index = Utf16Index::new(text);
+///
+/// assert_eq!(index.len_utf16(), 4);
+/// assert_eq!(index.span_to_utf16(Span::from(1..6)), Ok(Span::from(1..4)));
+/// assert_eq!(index.span_from_utf16(Span::from(3..4)), Ok(Span::from(5..6)));
+///
+/// assert_eq!(index.byte_offset_of_utf16(2), Err(Utf16Error::InsideSurrogatePair));
+/// assert_eq!(index.utf16_offset_of(2), Err(Utf16Error::NotCharBoundary));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Utf16Index {
+    /// Every non-ASCII character of the string
+    chars: Vec<WideChar>,
+    len: usize,
+    len_utf16: usize,
+}
+
+/// A character whose UTF-8 and UTF-16 offsets differ from those of an ASCII character.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug)]
+struct WideChar {
+    byte: usize,
+    utf16: usize,
+    len_utf8: u8,
+    len_utf16: u8,
+}
+
+#[cfg(feature = "alloc")]
+impl WideChar {
+    fn end(self) -> (usize, usize) {
+        (
+            self.byte + usize::from(self.len_utf8),
+            self.utf16 + usize::from(self.len_utf16),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Utf16Index {
+    pub fn new(text: &str) -> Self {
+        let mut chars = Vec::new();
+        let mut utf16 = 0;
+
+        for (byte, c) in text.char_indices() {
+            if !c.is_ascii() {
+                chars.push(WideChar {
+                    byte,
+                    utf16,
+                    len_utf8: c.len_utf8() as u8,
+                    len_utf16: c.len_utf16() as u8,
+                });
+            }
+
+            utf16 += c.len_utf16();
+        }
+
+        Self {
+            chars,
+            len: text.len(),
+            len_utf16: utf16,
+        }
+    }
+
+    /// The length of the string in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The length of the string in UTF-16 code units.
+    pub fn len_utf16(&self) -> usize {
+        self.len_utf16
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Converts a byte offset to a UTF-16 offset.
+    pub fn utf16_offset_of(&self, byte_index: usize) -> Result<usize, Utf16Error> {
+        if byte_index > self.len {
+            return Err(Utf16Error::OutOfBounds);
+        }
+
+        let i = self.chars.partition_point(|c| c.byte < byte_index);
+        let Some(prev) = i.checked_sub(1).map(|i| self.chars[i]) else {
+            return Ok(byte_index);
+        };
+
+        let (end_byte, end_utf16) = prev.end();
+        if byte_index < end_byte {
+            return Err(Utf16Error::NotCharBoundary);
+        }
+
+        Ok(end_utf16 + (byte_index - end_byte))
+    }
+
+    /// Converts a UTF-16 offset to a byte offset.
+    pub fn byte_offset_of_utf16(&self, utf16_index: usize) -> Result<usize, Utf16Error> {
+        if utf16_index > self.len_utf16 {
+            return Err(Utf16Error::OutOfBounds);
+        }
+
+        let i = self.chars.partition_point(|c| c.utf16 < utf16_index);
+        let Some(prev) = i.checked_sub(1).map(|i| self.chars[i]) else {
+            return Ok(utf16_index);
+        };
+
+        let (end_byte, end_utf16) = prev.end();
+        if utf16_index < end_utf16 {
+            return Err(Utf16Error::InsideSurrogatePair);
+        }
+
+        Ok(end_byte + (utf16_index - end_utf16))
+    }
+
+    /// Converts a span of bytes to a span of UTF-16 code units.
+    pub fn span_to_utf16(&self, span: Span) -> Result<Span, Utf16Error> {
+        Ok(Span::new(
+            self.utf16_offset_of(span.start)?,
+            self.utf16_offset_of(span.end)?,
+        ))
+    }
+
+    /// Converts a span of UTF-16 code units to a span of bytes.
+    pub fn span_from_utf16(&self, span: Span) -> Result<Span, Utf16Error> {
+        Ok(Span::new(
+            self.byte_offset_of_utf16(span.start)?,
+            self.byte_offset_of_utf16(span.end)?,
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_matches_scan() {
+        let text = "a\u{e9}\u{2192}\u{1f600}b\u{10348}";
+        let index = Utf16Index::new(text);
+
+        for byte in 0..=text.len() + 1 {
+            assert_eq!(
+                index.utf16_offset_of(byte),
+                utf16_offset_of(text, byte),
+                "{byte}"
+            );
+        }
+
+        for utf16 in 0..=index.len_utf16() + 1 {
+            assert_eq!(
+                index.byte_offset_of_utf16(utf16),
+                byte_offset_of_utf16(text, utf16),
+                "{utf16}"
+            );
+        }
+
+        assert_eq!(index.len_utf16(), text.encode_utf16().count());
+    }
+}
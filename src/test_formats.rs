@@ -0,0 +1,995 @@
+//! Minimal serde formats used to test the `serde` feature.
+//!
+//! `json` is a self-describing text format supporting a subset of JSON. `binary` is a
+//! non-self-describing format that writes integers with their native width in little endian.
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl serde::ser::StdError for Error {}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+fn unsupported<T>(what: &str) -> Result<T, Error> {
+    Err(Error(alloc::format!("{what} is not supported")))
+}
+
+pub mod json {
+    use alloc::string::String;
+    use core::fmt::Write;
+
+    use serde::{
+        Deserialize, Serialize,
+        de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+        ser::{self, Impossible},
+    };
+
+    use super::{Error, unsupported};
+
+    pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+        let mut out = String::new();
+        value.serialize(&mut Serializer::new(&mut out))?;
+        Ok(out)
+    }
+
+    pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str) -> Result<T, Error> {
+        let mut deserializer = Deserializer::new(input);
+        let value = T::deserialize(&mut deserializer)?;
+
+        if !deserializer.input.is_empty() {
+            return Err(Error("trailing characters".into()));
+        }
+
+        Ok(value)
+    }
+
+    pub struct Serializer<'a> {
+        out: &'a mut String,
+    }
+
+    impl<'a> Serializer<'a> {
+        pub fn new(out: &'a mut String) -> Self {
+            Self { out }
+        }
+    }
+
+    pub struct Compound<'s, 'a> {
+        ser: &'s mut Serializer<'a>,
+        first: bool,
+        close: char,
+    }
+
+    impl Compound<'_, '_> {
+        fn separator(&mut self) {
+            if !self.first {
+                self.ser.out.push(',');
+            }
+            self.first = false;
+        }
+    }
+
+    impl<'s, 'a> ser::Serializer for &'s mut Serializer<'a> {
+        type Ok = ();
+        type Error = Error;
+
+        type SerializeSeq = Compound<'s, 'a>;
+        type SerializeTuple = Compound<'s, 'a>;
+        type SerializeTupleStruct = Compound<'s, 'a>;
+        type SerializeTupleVariant = Impossible<(), Error>;
+        type SerializeMap = Compound<'s, 'a>;
+        type SerializeStruct = Compound<'s, 'a>;
+        type SerializeStructVariant = Impossible<(), Error>;
+
+        fn serialize_bool(self, v: bool) -> Result<(), Error> {
+            self.out.push_str(if v { "true" } else { "false" });
+            Ok(())
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<(), Error> {
+            self.serialize_i64(v.into())
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<(), Error> {
+            self.serialize_i64(v.into())
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<(), Error> {
+            self.serialize_i64(v.into())
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<(), Error> {
+            write!(self.out, "{v}").unwrap();
+            Ok(())
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<(), Error> {
+            self.serialize_u64(v.into())
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<(), Error> {
+            self.serialize_u64(v.into())
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<(), Error> {
+            self.serialize_u64(v.into())
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<(), Error> {
+            write!(self.out, "{v}").unwrap();
+            Ok(())
+        }
+
+        fn serialize_f32(self, _: f32) -> Result<(), Error> {
+            unsupported("f32")
+        }
+
+        fn serialize_f64(self, _: f64) -> Result<(), Error> {
+            unsupported("f64")
+        }
+
+        fn serialize_char(self, v: char) -> Result<(), Error> {
+            self.serialize_str(v.encode_utf8(&mut [0; 4]))
+        }
+
+        fn serialize_str(self, v: &str) -> Result<(), Error> {
+            if v.contains(['"', '\\']) {
+                return unsupported("escaping");
+            }
+
+            self.out.push('"');
+            self.out.push_str(v);
+            self.out.push('"');
+            Ok(())
+        }
+
+        fn serialize_bytes(self, _: &[u8]) -> Result<(), Error> {
+            unsupported("bytes")
+        }
+
+        fn serialize_none(self) -> Result<(), Error> {
+            self.serialize_unit()
+        }
+
+        fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_unit(self) -> Result<(), Error> {
+            self.out.push_str("null");
+            Ok(())
+        }
+
+        fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+            self.serialize_unit()
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+        ) -> Result<(), Error> {
+            self.serialize_str(variant)
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<(), Error> {
+            unsupported("newtype variant")
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            self.out.push('[');
+            Ok(Compound {
+                ser: self,
+                first: true,
+                close: ']',
+            })
+        }
+
+        fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeTupleStruct, Error> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            unsupported("tuple variant")
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            self.out.push('{');
+            Ok(Compound {
+                ser: self,
+                first: true,
+                close: '}',
+            })
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeStruct, Error> {
+            self.serialize_map(Some(len))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            unsupported("struct variant")
+        }
+    }
+
+    impl ser::SerializeSeq for Compound<'_, '_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            self.separator();
+            value.serialize(&mut *self.ser)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            self.ser.out.push(self.close);
+            Ok(())
+        }
+    }
+
+    impl ser::SerializeTuple for Compound<'_, '_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeTupleStruct for Compound<'_, '_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeMap for Compound<'_, '_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+            self.separator();
+            key.serialize(&mut *self.ser)?;
+            self.ser.out.push(':');
+            Ok(())
+        }
+
+        fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            value.serialize(&mut *self.ser)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeStruct for Compound<'_, '_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            ser::SerializeMap::serialize_entry(self, key, value)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    pub struct Deserializer<'de> {
+        input: &'de str,
+    }
+
+    impl<'de> Deserializer<'de> {
+        pub fn new(input: &'de str) -> Self {
+            Self { input }
+        }
+
+        fn peek(&self) -> Result<char, Error> {
+            self.input
+                .chars()
+                .next()
+                .ok_or_else(|| Error("unexpected end of input".into()))
+        }
+
+        fn eat(&mut self, c: char) -> bool {
+            if let Some(rest) = self.input.strip_prefix(c) {
+                self.input = rest;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn expect(&mut self, c: char) -> Result<(), Error> {
+            if self.eat(c) {
+                Ok(())
+            } else {
+                Err(Error(alloc::format!("expected `{c}`")))
+            }
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.peek()? {
+                '{' => {
+                    self.input = &self.input[1..];
+                    let value = visitor.visit_map(Elements::new(self, '}'))?;
+                    self.expect('}')?;
+                    Ok(value)
+                }
+                '[' => {
+                    self.input = &self.input[1..];
+                    let value = visitor.visit_seq(Elements::new(self, ']'))?;
+                    self.expect(']')?;
+                    Ok(value)
+                }
+                '"' => {
+                    let (s, rest) = self.input[1..]
+                        .split_once('"')
+                        .ok_or_else(|| Error("unterminated string".into()))?;
+                    self.input = rest;
+                    visitor.visit_borrowed_str(s)
+                }
+                '0'..='9' => {
+                    let len = self
+                        .input
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(self.input.len());
+                    let (digits, rest) = self.input.split_at(len);
+                    self.input = rest;
+                    visitor.visit_u64(digits.parse().map_err(|_| Error("overflow".into()))?)
+                }
+                _ => {
+                    for (word, value) in
+                        [("null", None), ("true", Some(true)), ("false", Some(false))]
+                    {
+                        if let Some(rest) = self.input.strip_prefix(word) {
+                            self.input = rest;
+                            return match value {
+                                None => visitor.visit_unit(),
+                                Some(b) => visitor.visit_bool(b),
+                            };
+                        }
+                    }
+
+                    Err(Error("unexpected character".into()))
+                }
+            }
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            if let Some(rest) = self.input.strip_prefix("null") {
+                self.input = rest;
+                visitor.visit_none()
+            } else {
+                visitor.visit_some(self)
+            }
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    struct Elements<'a, 'de> {
+        de: &'a mut Deserializer<'de>,
+        close: char,
+        first: bool,
+    }
+
+    impl<'a, 'de> Elements<'a, 'de> {
+        fn new(de: &'a mut Deserializer<'de>, close: char) -> Self {
+            Self {
+                de,
+                close,
+                first: true,
+            }
+        }
+
+        fn has_next(&mut self) -> Result<bool, Error> {
+            if self.de.peek()? == self.close {
+                return Ok(false);
+            }
+
+            if !self.first {
+                self.de.expect(',')?;
+            }
+            self.first = false;
+
+            Ok(true)
+        }
+    }
+
+    impl<'de> SeqAccess<'de> for Elements<'_, 'de> {
+        type Error = Error;
+
+        fn next_element_seed<T: DeserializeSeed<'de>>(
+            &mut self,
+            seed: T,
+        ) -> Result<Option<T::Value>, Error> {
+            if !self.has_next()? {
+                return Ok(None);
+            }
+
+            seed.deserialize(&mut *self.de).map(Some)
+        }
+    }
+
+    impl<'de> MapAccess<'de> for Elements<'_, 'de> {
+        type Error = Error;
+
+        fn next_key_seed<K: DeserializeSeed<'de>>(
+            &mut self,
+            seed: K,
+        ) -> Result<Option<K::Value>, Error> {
+            if !self.has_next()? {
+                return Ok(None);
+            }
+
+            let key = seed.deserialize(&mut *self.de)?;
+            self.de.expect(':')?;
+
+            Ok(Some(key))
+        }
+
+        fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+            seed.deserialize(&mut *self.de)
+        }
+    }
+}
+
+pub mod binary {
+    use alloc::vec::Vec;
+
+    use serde::{
+        Deserialize, Serialize,
+        de::{self, DeserializeSeed, SeqAccess, Visitor},
+        ser::{self, Impossible},
+    };
+
+    use super::{Error, unsupported};
+
+    pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+        let mut out = Vec::new();
+        value.serialize(&mut Serializer::new(&mut out))?;
+        Ok(out)
+    }
+
+    pub fn from_bytes<'de, T: Deserialize<'de>>(input: &'de [u8]) -> Result<T, Error> {
+        let mut deserializer = Deserializer::new(input);
+        let value = T::deserialize(&mut deserializer)?;
+
+        if !deserializer.input.is_empty() {
+            return Err(Error("trailing bytes".into()));
+        }
+
+        Ok(value)
+    }
+
+    pub struct Serializer<'a> {
+        out: &'a mut Vec<u8>,
+    }
+
+    impl<'a> Serializer<'a> {
+        pub fn new(out: &'a mut Vec<u8>) -> Self {
+            Self { out }
+        }
+
+        fn write_len(&mut self, len: usize) {
+            self.out.extend((len as u64).to_le_bytes());
+        }
+    }
+
+    impl<'s, 'a> ser::Serializer for &'s mut Serializer<'a> {
+        type Ok = ();
+        type Error = Error;
+
+        type SerializeSeq = &'s mut Serializer<'a>;
+        type SerializeTuple = &'s mut Serializer<'a>;
+        type SerializeTupleStruct = &'s mut Serializer<'a>;
+        type SerializeTupleVariant = Impossible<(), Error>;
+        type SerializeMap = Impossible<(), Error>;
+        type SerializeStruct = &'s mut Serializer<'a>;
+        type SerializeStructVariant = Impossible<(), Error>;
+
+        fn serialize_bool(self, v: bool) -> Result<(), Error> {
+            self.serialize_u8(v.into())
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<(), Error> {
+            self.out.push(v);
+            Ok(())
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<(), Error> {
+            self.out.extend(v.to_le_bytes());
+            Ok(())
+        }
+
+        fn serialize_f32(self, _: f32) -> Result<(), Error> {
+            unsupported("f32")
+        }
+
+        fn serialize_f64(self, _: f64) -> Result<(), Error> {
+            unsupported("f64")
+        }
+
+        fn serialize_char(self, v: char) -> Result<(), Error> {
+            self.serialize_u32(v.into())
+        }
+
+        fn serialize_str(self, v: &str) -> Result<(), Error> {
+            self.serialize_bytes(v.as_bytes())
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+            self.write_len(v.len());
+            self.out.extend_from_slice(v);
+            Ok(())
+        }
+
+        fn serialize_none(self) -> Result<(), Error> {
+            self.serialize_u8(0)
+        }
+
+        fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+            self.serialize_u8(1)?;
+            value.serialize(self)
+        }
+
+        fn serialize_unit(self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            index: u32,
+            _: &'static str,
+        ) -> Result<(), Error> {
+            self.serialize_u32(index)
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<(), Error> {
+            unsupported("newtype variant")
+        }
+
+        fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            let Some(len) = len else {
+                return unsupported("sequence without a length");
+            };
+
+            self.write_len(len);
+            Ok(self)
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            Ok(self)
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, Error> {
+            Ok(self)
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            unsupported("tuple variant")
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            unsupported("map")
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, Error> {
+            Ok(self)
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            unsupported("struct variant")
+        }
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+    }
+
+    impl ser::SerializeSeq for &mut Serializer<'_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            value.serialize(&mut **self)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl ser::SerializeTuple for &mut Serializer<'_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            value.serialize(&mut **self)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl ser::SerializeTupleStruct for &mut Serializer<'_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+            value.serialize(&mut **self)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl ser::SerializeStruct for &mut Serializer<'_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            value.serialize(&mut **self)
+        }
+
+        fn end(self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    pub struct Deserializer<'de> {
+        input: &'de [u8],
+    }
+
+    impl<'de> Deserializer<'de> {
+        pub fn new(input: &'de [u8]) -> Self {
+            Self { input }
+        }
+
+        fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+            let bytes = self.take_slice(N)?;
+            Ok(bytes.try_into().unwrap())
+        }
+
+        fn take_slice(&mut self, len: usize) -> Result<&'de [u8], Error> {
+            if self.input.len() < len {
+                return Err(Error("unexpected end of input".into()));
+            }
+
+            let (bytes, rest) = self.input.split_at(len);
+            self.input = rest;
+            Ok(bytes)
+        }
+
+        fn take_len(&mut self) -> Result<usize, Error> {
+            usize::try_from(u64::from_le_bytes(self.take()?))
+                .map_err(|_| Error("length overflow".into()))
+        }
+    }
+
+    macro_rules! deserialize_int {
+        ($($method:ident => $visit:ident($t:ty)),* $(,)?) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                    visitor.$visit(<$t>::from_le_bytes(self.take()?))
+                }
+            )*
+        };
+    }
+
+    macro_rules! unsupported {
+        ($($method:ident),* $(,)?) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+                    unsupported(stringify!($method))
+                }
+            )*
+        };
+    }
+
+    impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+        type Error = Error;
+
+        deserialize_int! {
+            deserialize_i8 => visit_i8(i8),
+            deserialize_i16 => visit_i16(i16),
+            deserialize_i32 => visit_i32(i32),
+            deserialize_i64 => visit_i64(i64),
+            deserialize_u8 => visit_u8(u8),
+            deserialize_u16 => visit_u16(u16),
+            deserialize_u32 => visit_u32(u32),
+            deserialize_u64 => visit_u64(u64),
+        }
+
+        unsupported! {
+            deserialize_any, deserialize_f32, deserialize_f64, deserialize_map,
+            deserialize_identifier, deserialize_ignored_any,
+        }
+
+        fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.take::<1>()? {
+                [0] => visitor.visit_bool(false),
+                [1] => visitor.visit_bool(true),
+                _ => Err(Error("invalid bool".into())),
+            }
+        }
+
+        fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let c = char::from_u32(u32::from_le_bytes(self.take()?))
+                .ok_or_else(|| Error("invalid char".into()))?;
+            visitor.visit_char(c)
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let len = self.take_len()?;
+            let s = core::str::from_utf8(self.take_slice(len)?)
+                .map_err(|_| Error("invalid utf-8".into()))?;
+            visitor.visit_borrowed_str(s)
+        }
+
+        fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.deserialize_str(visitor)
+        }
+
+        fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let len = self.take_len()?;
+            visitor.visit_borrowed_bytes(self.take_slice(len)?)
+        }
+
+        fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.deserialize_bytes(visitor)
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.take::<1>()? {
+                [0] => visitor.visit_none(),
+                [1] => visitor.visit_some(self),
+                _ => Err(Error("invalid option tag".into())),
+            }
+        }
+
+        fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_unit()
+        }
+
+        fn deserialize_unit_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_unit()
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let len = self.take_len()?;
+            visitor.visit_seq(Elements { de: self, len })
+        }
+
+        fn deserialize_tuple<V: Visitor<'de>>(
+            self,
+            len: usize,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_seq(Elements { de: self, len })
+        }
+
+        fn deserialize_tuple_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            len: usize,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            self.deserialize_tuple(len, visitor)
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            fields: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            self.deserialize_tuple(fields.len(), visitor)
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            _: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Error> {
+            unsupported("enum")
+        }
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+    }
+
+    struct Elements<'a, 'de> {
+        de: &'a mut Deserializer<'de>,
+        len: usize,
+    }
+
+    impl<'de> SeqAccess<'de> for Elements<'_, 'de> {
+        type Error = Error;
+
+        fn next_element_seed<T: DeserializeSeed<'de>>(
+            &mut self,
+            seed: T,
+        ) -> Result<Option<T::Value>, Error> {
+            if self.len == 0 {
+                return Ok(None);
+            }
+
+            self.len -= 1;
+            seed.deserialize(&mut *self.de).map(Some)
+        }
+
+        fn size_hint(&self) -> Option<usize> {
+            Some(self.len)
+        }
+    }
+}
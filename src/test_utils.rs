@@ -0,0 +1,13 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use core::cell::Cell;
+
+/// Counts how many times it was dropped.
+#[derive(Debug)]
+pub struct DropCounter<'a>(pub &'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
@@ -61,3 +61,45 @@ impl<T> DerefMut for DontDrop<T> {
         self.as_mut()
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for DontDrop<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes the inner value and wraps it in a `DontDrop`.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for DontDrop<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(all(test, feature = "serde", feature = "alloc"))]
+mod tests {
+    use alloc::{string::String, vec};
+
+    use super::DontDrop;
+    use crate::test_formats::{binary, json};
+
+    #[test]
+    fn serde() {
+        let value = DontDrop::new(vec![String::from("a"), String::from("bc")]);
+
+        let text = json::to_string(&value).unwrap();
+        assert_eq!(text, r#"["a","bc"]"#);
+        let parsed: DontDrop<vec::Vec<String>> = json::from_str(&text).unwrap();
+        assert_eq!(*parsed, *value);
+
+        let bytes = binary::to_bytes(&value).unwrap();
+        let parsed: DontDrop<vec::Vec<String>> = binary::from_bytes(&bytes).unwrap();
+        assert_eq!(*parsed, *value);
+
+        unsafe {
+            value.drop();
+            parsed.drop();
+        }
+    }
+}
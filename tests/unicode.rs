@@ -0,0 +1,79 @@
+//! Conformance tests for the `unicode` feature against the Unicode test data in `tests/data`.
+
+#![cfg(feature = "unicode")]
+
+use wutil::{prelude::*, span::Span};
+
+/// Parses a list of space separated hex code points.
+fn parse_code_points<'a>(code_points: impl Iterator<Item = &'a str>) -> String {
+    code_points
+        .map(|cp| char::from_u32(u32::from_str_radix(cp, 16).unwrap()).unwrap())
+        .collect()
+}
+
+#[test]
+fn grapheme_break_test() {
+    let data = include_str!("data/GraphemeBreakTest.txt");
+    let mut cases = 0;
+
+    for line in data.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let expected: Vec<String> = line
+            .trim_matches('÷')
+            .split('÷')
+            .map(|grapheme| parse_code_points(grapheme.split('×').map(str::trim)))
+            .collect();
+        let text = expected.concat();
+
+        let actual: Vec<&str> = text.grapheme_indices_spans().map(|(_, g)| g).collect();
+        assert_eq!(actual, expected, "{line}");
+
+        for (span, _) in text.grapheme_indices_spans() {
+            for byte_index in span {
+                assert_eq!(text.grapheme_span(byte_index), Some(span), "{line}");
+            }
+        }
+
+        cases += 1;
+    }
+
+    assert!(cases > 700);
+}
+
+#[test]
+fn emoji_test() {
+    let data = include_str!("data/emoji-test.txt");
+    let mut cases = 0;
+
+    for line in data.lines() {
+        let Some((code_points, status)) = line.split('#').next().unwrap().split_once(';') else {
+            continue;
+        };
+
+        if !matches!(status.trim(), "fully-qualified" | "component") {
+            continue;
+        }
+
+        let emoji = parse_code_points(code_points.split_whitespace());
+
+        assert_eq!(
+            emoji.grapheme_span(0),
+            Some(Span::from(0..emoji.len())),
+            "{line}"
+        );
+        assert_eq!(emoji.display_width(), 2, "{line}");
+
+        // Components like skin tones would join a preceding letter, but not a line feed
+        let text = format!("\n{emoji}b");
+        assert_eq!(text.byte_to_column(1 + emoji.len()), Some(2), "{line}");
+        assert_eq!(text.column_to_byte(1), Some(1), "{line}");
+
+        cases += 1;
+    }
+
+    assert!(cases > 3000);
+}